@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_2024::days::day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Malformed input must be rejected by the parser, never panic in the solver
+    if day02::parse_reports(input).is_ok() {
+        day02::solve(input);
+    }
+});
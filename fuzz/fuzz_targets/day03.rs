@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc_2024::days::day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Day 3 input is corrupted memory, so every string is valid input
+    day03::solve(input);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc_2024::{days::day04, utils::grid::Grid};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Ragged or non-ASCII grids must be rejected up front, never panic in the solver
+    if Grid::parse(input).is_ok() {
+        day04::solve(input);
+    }
+});
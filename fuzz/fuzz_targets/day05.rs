@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc_2024::days::day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // Malformed input must be rejected by the parser, never panic in the solver
+    let Ok((rules, updates)) = day05::parse_input(input) else {
+        return;
+    };
+
+    // Cyclic rules are reported as errors by the engine, the solver may still panic on them
+    if updates.iter().all(|update| rules.sort(&update.pages).is_ok()) {
+        day05::solve(input);
+    }
+});
@@ -0,0 +1,42 @@
+#![no_main]
+
+use aoc_2024::utils::{
+    cells::Cells,
+    direction::ALL_DIRECTIONS,
+    grid::Grid,
+    position::Position,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, isize, isize, u8)| {
+    let (text, row, col, direction) = input;
+    let grid = Grid::new(text);
+
+    let _ = grid.get_signed(row, col);
+    let _ = grid.to_string();
+
+    for (pos, value) in grid.iter_2d() {
+        // Every position the iterator yields must be readable back from the grid
+        assert_eq!(grid.get_at(pos), Some(value));
+
+        for direction in ALL_DIRECTIONS {
+            if let Some(next) = grid.position_in_direction(pos, direction) {
+                assert!(grid.get_at(next).is_some());
+            }
+        }
+    }
+
+    // Arbitrary start positions, including ones far outside the grid
+    let direction = ALL_DIRECTIONS[direction as usize % ALL_DIRECTIONS.len()];
+    let _ = grid.position_in_direction(Position::new(row, col), direction);
+
+    // A grid that validates must hold exactly the non-newline bytes of the input,
+    // less any blank or whitespace-only lines at the end
+    if let Ok(parsed) = Grid::parse(text) {
+        let mut rows: Vec<&str> = text.lines().collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
+        }
+        assert_eq!(parsed._as_bytes(), rows.concat().as_bytes());
+    }
+});
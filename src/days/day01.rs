@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 
-use crate::{Solution, SolutionPair};
+use crate::{
+    Solution, SolutionPair,
+    utils::{ints::exact_strict_integers, parse::ParseError},
+};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-pub fn subtract_vectors(left: &[i32], right: &[i32]) -> Vec<i32> {
+pub fn subtract_vectors(left: &[i32], right: &[i32]) -> Vec<u32> {
     left.iter()
         .zip(right.iter())
-        .map(|(x, y)| (*x - *y).abs())
+        .map(|(x, y)| x.abs_diff(*y))
         .collect()
 }
 
@@ -20,32 +23,40 @@ fn build_frequency_map(list: &[i32]) -> HashMap<&i32, i32> {
     map
 }
 
-pub fn solve(input: &str) -> SolutionPair {
+pub fn parse_lists(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut left: Vec<i32> = Vec::new();
     let mut right: Vec<i32> = Vec::new();
-    for line in input.lines() {
-        if line.len() >= 13 {
-            let left_num = &line[0..5];
-            let right_num = &line[8..13];
-
-            left.push(left_num.parse::<i32>().unwrap());
-            right.push(right_num.parse::<i32>().unwrap());
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
+
+        let [left_num, right_num] =
+            exact_strict_integers::<i32, 2>(line).map_err(|e| e.at_line(index + 1))?;
+        left.push(left_num);
+        right.push(right_num);
     }
 
+    Ok((left, right))
+}
+
+pub fn solve(input: &str) -> SolutionPair {
+    let (mut left, mut right) =
+        parse_lists(input).unwrap_or_else(|e| panic!("Invalid day 1 input: {e}"));
+
     // p1
     left.sort();
     right.sort();
     let distances = subtract_vectors(&left, &right);
-    let sol1: i32 = distances.iter().sum();
+    let sol1: u64 = distances.iter().map(|&d| u64::from(d)).sum();
 
     // p2
     let frequency_map = build_frequency_map(&right);
-    let sol2: i32 = left
+    let sol2: i64 = left
         .iter()
         .map(|x| {
             let mult = frequency_map.get(x).unwrap_or(&0);
-            x * mult
+            i64::from(*x) * i64::from(*mult)
         })
         .sum();
 
@@ -77,4 +88,22 @@ mod tests {
         let result = subtract_vectors(&left, &right);
         assert_eq!(result, vec![4, 8, 5]);
     }
+
+    #[test]
+    fn test_parse_lists_rejects_garbage() {
+        let err = parse_lists("10000   20000\n1000é   30000").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_parse_lists_rejects_extra_column() {
+        let err = parse_lists("10000   20000\n10000   30000   40000").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_parse_lists_rejects_missing_column() {
+        let err = parse_lists("10000   20000\n30000").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
 }
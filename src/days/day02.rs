@@ -1,12 +1,22 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    Solution, SolutionPair,
+    utils::{ints::strict_integers, parse::ParseError},
+};
 
 ///////////////////////////////////////////////////////////////////////////////
-fn create_levels_vec(line: &str) -> Vec<i32> {
-    let levels: Vec<i32> = line
-        .split_whitespace()
-        .map(|x| x.parse::<i32>().unwrap())
-        .collect();
-    levels
+fn create_levels_vec(line_number: usize, line: &str) -> Result<Vec<i32>, ParseError> {
+    strict_integers::<i32>(line)
+        .map(|level| level.map_err(|e| e.at_line(line_number)))
+        .collect()
+}
+
+pub fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| create_levels_vec(index + 1, line))
+        .collect()
 }
 
 fn create_slice_vec(levels: &[i32], exclude: usize) -> Vec<i32> {
@@ -20,6 +30,11 @@ fn create_slice_vec(levels: &[i32], exclude: usize) -> Vec<i32> {
 }
 
 fn evaluate_levels(levels: &[i32]) -> bool {
+    // A report with fewer than two levels can't break either rule
+    if levels.len() < 2 {
+        return true;
+    }
+
     let is_ascending = levels[0] < levels[1];
     for (index, level) in levels.iter().enumerate() {
         if index == levels.len() - 1 {
@@ -28,10 +43,10 @@ fn evaluate_levels(levels: &[i32]) -> bool {
 
         let next_element = levels[index + 1];
         if is_ascending {
-            if *level >= next_element || next_element - *level > 3 {
+            if *level >= next_element || level.abs_diff(next_element) > 3 {
                 return false;
             }
-        } else if *level <= next_element || *level - next_element > 3 {
+        } else if *level <= next_element || level.abs_diff(next_element) > 3 {
             return false;
         }
     }
@@ -51,11 +66,12 @@ fn deep_evaluate_levels(levels: &[i32]) -> bool {
 }
 
 pub fn solve(input: &str) -> SolutionPair {
+    let reports = parse_reports(input).unwrap_or_else(|e| panic!("Invalid day 2 input: {e}"));
+
     // p1
     let mut p1_count = 0;
-    for line in input.lines() {
-        let levels = create_levels_vec(line);
-        if evaluate_levels(&levels) {
+    for levels in &reports {
+        if evaluate_levels(levels) {
             p1_count += 1
         }
     }
@@ -63,9 +79,8 @@ pub fn solve(input: &str) -> SolutionPair {
 
     // p2
     let mut p2_count = 0;
-    for line in input.lines() {
-        let levels = create_levels_vec(line);
-        if evaluate_levels(&levels) || deep_evaluate_levels(&levels) {
+    for levels in &reports {
+        if evaluate_levels(levels) || deep_evaluate_levels(levels) {
             p2_count += 1;
         }
     }
@@ -93,4 +108,23 @@ mod tests {
         let p2_result = format!("{p2}");
         assert_eq!(p2_result, "4");
     }
+
+    #[test]
+    fn test_parse_reports_rejects_garbage() {
+        let err = parse_reports("7 6 4 2 1\n1 2 x 8 9").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_parse_reports_rejects_overflow() {
+        let err = parse_reports("7 6 4 2 1\n1 2 99999999999 8 9").unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_single_level_report_is_safe() {
+        let (p1, p2) = solve("5\n");
+        assert_eq!(format!("{p1}"), "1");
+        assert_eq!(format!("{p2}"), "1");
+    }
 }
@@ -1,26 +1,20 @@
 use regex::{Match, Regex};
 
-use crate::{Solution, SolutionPair};
+use crate::{Solution, SolutionPair, utils::ints::exact_integers};
 
 ///////////////////////////////////////////////////////////////////////////////
 pub fn get_operands_from_match(input: &str) -> (i32, i32) {
-    let re = Regex::new(r"\d+").unwrap();
-    let operands: Vec<i32> = re
-        .find_iter(input)
-        .map(|mat| {
-            let operand_str: &str = mat.into();
-            operand_str.parse().unwrap()
-        })
-        .collect();
-
-    (operands[0], operands[1])
+    let [a, b] = exact_integers::<i32, 2>(input).expect("mul match has two 1-3 digit operands");
+    (a, b)
 }
 
 pub fn is_do_active(mat: Match<'_>, dos_and_donts_map: &[(usize, bool)]) -> bool {
     let mult_start = mat.start();
 
-    if mult_start < dos_and_donts_map[0].0 {
-        return true;
+    // Multiplications are enabled until the first do() or don't()
+    match dos_and_donts_map.first() {
+        Some(&(first_start, _)) if mult_start > first_start => {}
+        _ => return true,
     }
 
     let closest_index_after = binary_search(dos_and_donts_map, mult_start);
@@ -48,12 +42,13 @@ pub fn binary_search(slice: &[(usize, bool)], target: usize) -> usize {
 
 pub fn solve(input: &str) -> SolutionPair {
     // p1
-    let re = Regex::new(r"mul\(\d{1,},\d{1,}\)").unwrap();
-    let sol1: i32 = re
+    // Operands are 1-3 digit numbers, anything longer is corrupted memory
+    let re = Regex::new(r"mul\(\d{1,3},\d{1,3}\)").unwrap();
+    let sol1: i64 = re
         .find_iter(input)
         .map(|mat| {
             let (a, b) = get_operands_from_match(mat.into());
-            a * b
+            i64::from(a) * i64::from(b)
         })
         .sum();
 
@@ -79,12 +74,12 @@ pub fn solve(input: &str) -> SolutionPair {
 
     // p2
     dos_and_donts_map.sort_by_key(|&(start, _)| start);
-    let sol2: i32 = re
+    let sol2: i64 = re
         .find_iter(input)
         .map(|mat| {
             if is_do_active(mat, &dos_and_donts_map) {
                 let (a, b) = get_operands_from_match(mat.into());
-                a * b
+                i64::from(a) * i64::from(b)
             } else {
                 0
             }
@@ -114,6 +109,20 @@ mod tests {
         assert_eq!(p2_result, "48");
     }
 
+    #[test]
+    fn test_no_dos_or_donts_p2() {
+        let (_, p2) = solve("mul(2,4)mul(3,3)");
+        let p2_result = format!("{p2}");
+        assert_eq!(p2_result, "17");
+    }
+
+    #[test]
+    fn test_operands_longer_than_three_digits_ignored() {
+        let (p1, _) = solve("mul(99999999999,2)mul(123,2)");
+        let p1_result = format!("{p1}");
+        assert_eq!(p1_result, "246");
+    }
+
     #[test]
     fn test_get_operands() {
         let input = "mul(2, 44)";
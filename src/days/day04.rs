@@ -1,134 +1,62 @@
 use crate::{
     Solution, SolutionPair,
-    utils::grid::{ALL_DIRECTIONS, Directions, Grid, Point},
+    utils::{
+        grid::Grid,
+        render::Color,
+        visualize::{self, Snapshot},
+        word_search::{Match, WordSearch},
+    },
 };
 
 ///////////////////////////////////////////////////////////////////////////////
 
-// Could optimize visited hashmap in to avoid searching letters part of existing solutions
-struct WordSearch {
-    grid: Grid,
-}
-
-impl WordSearch {
-    pub const WORD: &str = "XMAS";
-
-    fn get_next_match_with_direction(
-        &self,
-        curr_point: &Point,
-        next_letter: &u8,
-        direction: &Directions,
-    ) -> Option<Point> {
-        // if direction provided, check if theres the next letter in that direction
-        if let Some(point_in_direction) = self.grid.get_point_in_direction(curr_point, direction)
-            && &point_in_direction.value == next_letter
-        {
-            return Some(point_in_direction);
-        }
-
-        None
-    }
-
-    fn get_next_letter(letter: &u8) -> Option<u8> {
-        match letter {
-            b'X' => Some(b'M'),
-            b'M' => Some(b'A'),
-            b'A' => Some(b'S'),
-            b'S' => None,
-            _ => panic!("Unsupported letter in input"),
-        }
-    }
-
-    fn evaluate_matching_word_p1(&self, point: &Point) -> i32 {
-        // Always start with X, otherwise we don't have a match
-        if point.value != b'X' {
-            return 0;
-        }
-
-        let mut curr_point: Point;
-        let mut count = 0;
-
-        for direction in ALL_DIRECTIONS {
-            curr_point = *point;
-            for letter in Self::WORD.bytes() {
-                if let Some(next_letter) = Self::get_next_letter(&letter) {
-                    if let Some(next_match_point) =
-                        self.get_next_match_with_direction(&curr_point, &next_letter, &direction)
-                    {
-                        // Move cursor to current point
-                        curr_point = next_match_point;
-                    } else {
-                        break;
-                    }
-                } else {
-                    count += 1;
-                }
-            }
-        }
-
-        count
-    }
-
-    fn evaluate_matching_word_p2(&self, point: &Point) -> i32 {
-        // looking for X shape of 2 mas words, forward or backwards
-        // approach: get all points for the x shape, split into 2 words
-        // check if those words are 1. all Some(), and 2. spell mas or sam
-
-        // Always start with A, otherwise we don't have a match
-        if point.value != b'A' {
-            return 0;
-        }
+pub fn solve(input: &str) -> SolutionPair {
+    let grid = Grid::parse(input).unwrap_or_else(|e| panic!("Invalid day 4 input: {e}"));
 
-        let (top_left, top_right, bottom_left, bottom_right) = (
-            self.grid
-                .get_point_in_direction(point, &Directions::TopLeft),
-            self.grid
-                .get_point_in_direction(point, &Directions::TopRight),
-            self.grid
-                .get_point_in_direction(point, &Directions::BottomLeft),
-            self.grid
-                .get_point_in_direction(point, &Directions::BottomRight),
-        );
+    // XMAS in any of the 8 directions, overlaps included
+    let xmas = WordSearch::new().word("XMAS").find_all(&grid);
+    record_scan(&grid, "Part 1", &xmas);
+    let sol1 = xmas.len();
 
-        if let (Some(top_left), Some(top_right), Some(bottom_left), Some(bottom_right)) =
-            (top_left, top_right, bottom_left, bottom_right)
-        {
-            let x1: String = [top_left.value as char, 'A', bottom_right.value as char]
-                .iter()
-                .collect();
-            let x2: String = [bottom_left.value as char, 'A', top_right.value as char]
-                .iter()
-                .collect();
+    // Two MAS crossing on their A, each forwards or backwards
+    let crosses = WordSearch::new().shape("M.S\n.A.\nM.S").find_all(&grid);
+    record_scan(&grid, "Part 2", &crosses);
+    let sol2 = crosses.len();
 
-            if (x1 == "MAS" || x1 == "SAM") && (x2 == "MAS" || x2 == "SAM") {
-                return 1;
-            }
-        }
-
-        0
-    }
+    (Solution::from(sol1), Solution::from(sol2))
 }
 
-pub fn solve(input: &str) -> SolutionPair {
-    let grid = Grid::new(input);
-    let word_search = WordSearch { grid };
-
-    let mut sol1 = 0;
-    for point in word_search.grid.iter_2d() {
-        sol1 += word_search.evaluate_matching_word_p1(&point)
+// One frame per grid row, with every match found so far highlighted
+fn record_scan(grid: &Grid, part: &str, matches: &[Match]) {
+    if !visualize::is_enabled() {
+        return;
     }
 
-    let mut sol2 = 0;
-    for point in word_search.grid.iter_2d() {
-        sol2 += word_search.evaluate_matching_word_p2(&point)
+    for row in 0..grid.height() as isize {
+        let found = matches.partition_point(|m| m.start.row <= row);
+        visualize::record(|| {
+            matches[..found]
+                .iter()
+                .zip(Color::ALL.iter().cycle())
+                .fold(Snapshot::new(grid), |snapshot, (m, &color)| {
+                    snapshot.highlight(m.cells.iter().copied(), color)
+                })
+                .caption(format!("{part}: row {row}, {found} found"))
+        });
     }
-
-    (Solution::from(sol1), Solution::from(sol2))
+    visualize::note(|| format!("{part}: {} in total", matches.len()));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::position::Position;
+
+    fn matches_from(input: &str, start: Position) -> usize {
+        let grid = Grid::new(input);
+        let matches = WordSearch::new().word("XMAS").find_all(&grid);
+        matches.iter().filter(|m| m.start == start).count()
+    }
 
     #[test]
     fn test_example_input_p1() {
@@ -141,26 +69,14 @@ mod tests {
     #[test]
     fn test_example_input_4_0_right() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
-        let grid = Grid::new(input);
-        let word_search = WordSearch { grid };
-        let found = word_search.evaluate_matching_word_p1(&Point {
-            row: 4,
-            col: 0,
-            value: b'X',
-        });
+        let found = matches_from(input, Position::new(4, 0));
         assert_eq!(found, 1);
     }
 
     #[test]
     fn test_example_input_double_count() {
         let input = "XMASXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nSSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
-        let grid = Grid::new(input);
-        let word_search = WordSearch { grid };
-        let found = word_search.evaluate_matching_word_p1(&Point {
-            row: 0,
-            col: 0,
-            value: b'X',
-        });
+        let found = matches_from(input, Position::new(0, 0));
         assert_eq!(found, 2);
     }
 
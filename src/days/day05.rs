@@ -1,25 +1,65 @@
-use crate::{Solution, SolutionPair};
+use crate::{
+    Solution, SolutionPair,
+    utils::{
+        ordering::{OrderingError, OrderingRules},
+        parse::ParseError,
+        sections::{list, pair, split_exact_sections},
+    },
+};
 
 ///////////////////////////////////////////////////////////////////////////////
 
-struct Page {
-    pages: Vec<i32>,
+pub struct Page {
+    pub pages: Vec<u32>,
 }
 
-impl Page {
-    pub fn new(line: &str) -> Self {
-        let nums: Vec<i32> = line
-            .split(',')
-            .map(|num| num.parse::<i32>().unwrap())
-            .collect();
-        Self { pages: nums }
+fn middle_page(pages: &[u32]) -> u32 {
+    pages[pages.len() / 2]
+}
+
+pub fn parse_input(input: &str) -> Result<(OrderingRules<u32>, Vec<Page>), ParseError> {
+    let [rules_section, updates_section] = split_exact_sections::<2>(input)?;
+
+    let mut rules = OrderingRules::new();
+    let rule_pairs = rules_section.parse_lines(pair::<u32>('|'))?;
+    for ((line, _), (before, after)) in rules_section.lines().zip(rule_pairs) {
+        rules
+            .add_rule(before, after)
+            .map_err(|e| rules_section.error(line, e.to_string()))?;
     }
+
+    let updates = updates_section
+        .parse_lines(list::<u32>(','))?
+        .into_iter()
+        .map(|pages| Page { pages })
+        .collect();
+
+    Ok((rules, updates))
 }
 
 pub fn solve(input: &str) -> SolutionPair {
-    println!("Input: \n{input}");
-    let sol1 = 0;
-    let sol2 = 0;
+    let (rules, updates) =
+        parse_input(input).unwrap_or_else(|e| panic!("Invalid day 5 input: {e}"));
+
+    // p1
+    let sol1: u64 = updates
+        .iter()
+        .filter(|update| rules.is_ordered(&update.pages))
+        .map(|update| u64::from(middle_page(&update.pages)))
+        .sum();
+
+    // p2
+    let sol2: u64 = updates
+        .iter()
+        .filter(|update| !rules.is_ordered(&update.pages))
+        .map(|update| {
+            rules
+                .sort(&update.pages)
+                .map(|sorted| u64::from(middle_page(&sorted)))
+        })
+        .sum::<Result<u64, OrderingError<u32>>>()
+        .unwrap_or_else(|e| panic!("Invalid day 5 input: {e}"));
+
     (Solution::from(sol1), Solution::from(sol2))
 }
 
@@ -27,11 +67,48 @@ pub fn solve(input: &str) -> SolutionPair {
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+
     #[test]
     fn test_example_input_day5_p1() {
-        let input = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+        let input = EXAMPLE;
         let (p1, _) = solve(input);
         let p1_result = format!("{p1}");
         assert_eq!(p1_result, "143");
     }
+
+    #[test]
+    fn test_example_input_day5_p2() {
+        let (_, p2) = solve(EXAMPLE);
+        let p2_result = format!("{p2}");
+        assert_eq!(p2_result, "123");
+    }
+
+    #[test]
+    fn test_missing_separator() {
+        let err = parse_input("47|53\n97|13").err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_contradictory_rules() {
+        let err = parse_input("47|53\n53|47\n\n47,53").err().unwrap();
+        assert_eq!(err.section, Some(1));
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "contradictory rules 53|47 and 47|53");
+    }
+
+    #[test]
+    fn test_crlf_input() {
+        let input = EXAMPLE.replace('\n', "\r\n") + "\r\n\r\n";
+        let (p1, p2) = solve(&input);
+        assert_eq!(format!("{p1}"), "143");
+        assert_eq!(format!("{p2}"), "123");
+    }
+
+    #[test]
+    #[should_panic(expected = "cyclic rules")]
+    fn test_cyclic_rules_reported() {
+        solve("1|2\n2|3\n3|1\n\n3,2,1");
+    }
 }
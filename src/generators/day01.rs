@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use crate::utils::rng::Rng;
+
+///////////////////////////////////////////////////////////////////////////////
+
+const LINES: usize = 1000;
+
+pub fn generate(scale: usize, rng: &mut Rng) -> String {
+    let lines = LINES * scale;
+    let left: Vec<u64> = (0..lines).map(|_| rng.range(10000, 100000)).collect();
+
+    let mut output = String::with_capacity(lines * 14);
+    for &left_id in &left {
+        // Reuse left IDs often enough that the similarity score in p2 is non-trivial
+        let right_id = if rng.chance(40) {
+            *rng.pick(&left)
+        } else {
+            rng.range(10000, 100000)
+        };
+        writeln!(output, "{left_id:05}   {right_id:05}").unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::parse_lists;
+
+    #[test]
+    fn test_generated_lists_parse() {
+        let input = generate(2, &mut Rng::new(5));
+        let (left, right) = parse_lists(&input).unwrap();
+        assert_eq!(left.len(), 2 * LINES);
+        assert_eq!(right.len(), 2 * LINES);
+    }
+}
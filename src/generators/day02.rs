@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+use crate::utils::rng::Rng;
+
+///////////////////////////////////////////////////////////////////////////////
+
+const REPORTS: usize = 1000;
+
+fn generate_report(rng: &mut Rng) -> Vec<i64> {
+    let len = rng.range(5, 9) as usize;
+    let ascending = rng.chance(50);
+    let mut level = rng.range(10, 90) as i64;
+
+    let mut levels = Vec::with_capacity(len);
+    for _ in 0..len {
+        levels.push(level);
+        let step = rng.range(1, 4) as i64;
+        level += if ascending { step } else { -step };
+    }
+
+    // Break roughly half the reports, some of them beyond what the dampener fixes
+    if rng.chance(50) {
+        let faults = if rng.chance(50) { 1 } else { 2 };
+        for _ in 0..faults {
+            let index = rng.index(len);
+            levels[index] += rng.range(0, 9) as i64 - 4;
+        }
+    }
+
+    levels
+}
+
+pub fn generate(scale: usize, rng: &mut Rng) -> String {
+    let mut output = String::new();
+    for _ in 0..REPORTS * scale {
+        let report = generate_report(rng);
+        let line: Vec<String> = report.iter().map(|level| level.to_string()).collect();
+        writeln!(output, "{}", line.join(" ")).unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day02::parse_reports;
+
+    #[test]
+    fn test_generated_reports_parse() {
+        let input = generate(1, &mut Rng::new(5));
+        let reports = parse_reports(&input).unwrap();
+        assert_eq!(reports.len(), REPORTS);
+        assert!(reports.iter().all(|report| (5..9).contains(&report.len())));
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Write;
+
+use crate::utils::rng::Rng;
+
+///////////////////////////////////////////////////////////////////////////////
+
+const LINES: usize = 6;
+const LINE_LENGTH: usize = 3000;
+const NOISE: &[u8] = b"!@#$%^&*()[]{}<>?,;:'+-_/ whyselectfromwhenwhere";
+
+fn write_token(line: &mut String, rng: &mut Rng) {
+    let a = rng.range(1, 1000);
+    let b = rng.range(1, 1000);
+    match rng.range(0, 10) {
+        0..=2 => write!(line, "mul({a},{b})").unwrap(),
+        // Near misses the parser has to reject
+        3 => write!(line, "mul[{a},{b}]").unwrap(),
+        4 => write!(line, "mul({a}, {b})").unwrap(),
+        5 => write!(line, "mul({a},{b}").unwrap(),
+        6 => line.push_str("do()"),
+        7 => line.push_str("don't()"),
+        _ => {
+            for _ in 0..rng.range(1, 8) {
+                line.push(*rng.pick(NOISE) as char);
+            }
+        }
+    }
+}
+
+pub fn generate(scale: usize, rng: &mut Rng) -> String {
+    let mut output = String::new();
+    for _ in 0..LINES * scale {
+        let mut line = String::with_capacity(LINE_LENGTH + 16);
+        while line.len() < LINE_LENGTH {
+            write_token(&mut line, rng);
+        }
+        output.push_str(&line);
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_memory_contains_instructions() {
+        let input = generate(1, &mut Rng::new(5));
+        assert_eq!(input.lines().count(), LINES);
+        assert!(input.contains("mul("));
+        assert!(input.contains("do()"));
+        assert!(input.contains("don't()"));
+    }
+}
@@ -0,0 +1,34 @@
+use crate::utils::rng::Rng;
+
+///////////////////////////////////////////////////////////////////////////////
+
+const SIDE: usize = 140;
+const LETTERS: &[u8] = b"XMAS";
+
+pub fn generate(scale: usize, rng: &mut Rng) -> String {
+    // Scale the area rather than each side so the grid has N times the cells
+    let side = (SIDE as f64 * (scale as f64).sqrt()).round() as usize;
+
+    let mut output = String::with_capacity(side * (side + 1));
+    for _ in 0..side {
+        for _ in 0..side {
+            output.push(*rng.pick(LETTERS) as char);
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_grid_is_square() {
+        let input = generate(4, &mut Rng::new(5));
+        let rows: Vec<&str> = input.lines().collect();
+        assert_eq!(rows.len(), 2 * SIDE);
+        assert!(rows.iter().all(|row| row.len() == 2 * SIDE));
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt::Write;
+
+use crate::utils::rng::Rng;
+
+///////////////////////////////////////////////////////////////////////////////
+
+// The real input orders 49 pages with a rule for every pair
+const PAGES_PER_GROUP: u64 = 49;
+const UPDATES_PER_GROUP: usize = 200;
+
+// Page numbers of group g are g * 100 + 10 ..= g * 100 + 58, so groups never share pages
+fn group_pages(group: u64) -> Vec<u64> {
+    (0..PAGES_PER_GROUP).map(|i| group * 100 + 10 + i).collect()
+}
+
+pub fn generate(scale: usize, rng: &mut Rng) -> String {
+    // Scaling adds independent groups, keeping the rules for every update complete and acyclic
+    let orders: Vec<Vec<u64>> = (0..scale as u64)
+        .map(|group| {
+            let mut order = group_pages(group);
+            rng.shuffle(&mut order);
+            order
+        })
+        .collect();
+
+    let mut rules = Vec::new();
+    for order in &orders {
+        for (i, before) in order.iter().enumerate() {
+            for after in &order[i + 1..] {
+                rules.push((*before, *after));
+            }
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut output = String::new();
+    for (before, after) in rules {
+        writeln!(output, "{before}|{after}").unwrap();
+    }
+    output.push('\n');
+
+    for _ in 0..UPDATES_PER_GROUP * scale {
+        let order = rng.pick(&orders);
+        let len = 2 * rng.range(2, 12) as usize + 1;
+
+        let mut positions: Vec<usize> = (0..order.len()).collect();
+        rng.shuffle(&mut positions);
+        let mut update = positions[..len].to_vec();
+
+        // Roughly half the updates are already correctly ordered
+        if rng.chance(50) {
+            update.sort();
+        }
+
+        let pages: Vec<String> = update.iter().map(|&i| order[i].to_string()).collect();
+        writeln!(output, "{}", pages.join(",")).unwrap();
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_sections() {
+        let input = generate(2, &mut Rng::new(5));
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let pairs = (PAGES_PER_GROUP * (PAGES_PER_GROUP - 1) / 2) as usize;
+        assert_eq!(rules.lines().count(), 2 * pairs);
+        assert_eq!(updates.lines().count(), 2 * UPDATES_PER_GROUP);
+    }
+
+    #[test]
+    fn test_updates_have_odd_length() {
+        let input = generate(1, &mut Rng::new(5));
+        let (_, updates) = input.split_once("\n\n").unwrap();
+        assert!(updates.lines().all(|line| line.split(',').count() % 2 == 1));
+    }
+}
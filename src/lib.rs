@@ -0,0 +1,7 @@
+pub mod days;
+pub mod generators;
+pub mod utils;
+
+pub use utils::solution::Solution;
+
+pub type SolutionPair = (Solution, Solution);
@@ -1,13 +1,18 @@
-mod days;
-mod utils;
-
-use days::{day01, day02, day03, day04, day05};
+use aoc_2024::SolutionPair;
+use aoc_2024::days::{day01, day02, day03, day04, day05};
+use aoc_2024::generators;
+use aoc_2024::utils::visualize::{self, Frame};
 use std::env;
 use std::fs::read_to_string;
-use std::time::Instant;
-use utils::solution::Solution;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-pub type SolutionPair = (Solution, Solution);
+// Where recorded frames go with --visualize
+enum Playback {
+    Terminal(Duration),
+    Files(PathBuf),
+}
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -15,13 +20,47 @@ fn main() {
         panic!("Please provide the day(s) to run as a command-line argument.");
     }
 
-    let days: Vec<u8> = args[1..]
-        .iter()
-        .map(|x| {
-            x.parse()
-                .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-        })
-        .collect();
+    if args[1] == "gen" {
+        run_generator(&args[2..]);
+        return;
+    }
+
+    // <day>... [--visualize [--delay MS | --out DIR]]
+    let mut days: Vec<u8> = Vec::new();
+    let mut record = false;
+    let mut delay = Duration::from_millis(100);
+    let mut out_dir = None;
+
+    let mut flags = args[1..].iter();
+    while let Some(arg) = flags.next() {
+        match arg.as_str() {
+            "--visualize" => record = true,
+            "--delay" => {
+                let value = flags.next().expect("Missing value for --delay");
+                let ms = value
+                    .parse()
+                    .unwrap_or_else(|v| panic!("Not a valid delay: {}", v));
+                delay = Duration::from_millis(ms);
+            }
+            "--out" => {
+                out_dir = Some(PathBuf::from(
+                    flags.next().expect("Missing value for --out"),
+                ))
+            }
+            _ => days.push(
+                arg.parse()
+                    .unwrap_or_else(|v| panic!("Not a valid day: {}", v)),
+            ),
+        }
+    }
+
+    let playback = record.then_some(match out_dir {
+        Some(dir) => Playback::Files(dir),
+        None => Playback::Terminal(delay),
+    });
+    if record {
+        visualize::enable();
+    }
 
     let mut runtime = 0.0;
 
@@ -42,11 +81,29 @@ fn main() {
         println!("  · Elapsed: {:.4} ms", elapsed_ms);
 
         runtime += elapsed_ms;
+
+        if let Some(playback) = &playback {
+            play(day, &visualize::take_frames(), playback);
+        }
     }
 
     println!("Total runtime: {:.4} ms", runtime);
 }
 
+fn play(day: u8, frames: &[Frame], playback: &Playback) {
+    let result = match playback {
+        Playback::Terminal(delay) => {
+            let stdout = io::stdout();
+            let terminal = stdout.is_terminal();
+            visualize::replay(frames, *delay, terminal, &mut stdout.lock())
+        }
+        Playback::Files(dir) => {
+            visualize::write_frames(frames, &dir.join(format!("day{:02}", day)))
+        }
+    };
+    result.unwrap_or_else(|e| panic!("Failed to play day {} frames: {}", day, e));
+}
+
 fn get_day_solver(day: u8) -> fn(&str) -> SolutionPair {
     match day {
         1 => day01::solve,
@@ -57,3 +114,39 @@ fn get_day_solver(day: u8) -> fn(&str) -> SolutionPair {
         _ => unimplemented!(),
     }
 }
+
+// gen <day> [--scale N] [--seed S], writes the generated input to stdout
+fn run_generator(args: &[String]) {
+    let day: u8 = args
+        .first()
+        .expect("Please provide the day to generate input for.")
+        .parse()
+        .unwrap_or_else(|v| panic!("Not a valid day: {}", v));
+
+    let mut scale: usize = 1;
+    let mut seed: u64 = 2024;
+
+    let mut flags = args[1..].iter();
+    while let Some(flag) = flags.next() {
+        let value = flags
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {}", flag));
+        match flag.as_str() {
+            "--scale" => {
+                scale = value
+                    .parse()
+                    .unwrap_or_else(|v| panic!("Not a valid scale: {}", v))
+            }
+            "--seed" => {
+                seed = value
+                    .parse()
+                    .unwrap_or_else(|v| panic!("Not a valid seed: {}", v))
+            }
+            _ => panic!("Unknown option: {}", flag),
+        }
+    }
+
+    let input = generators::generate(day, scale, seed)
+        .unwrap_or_else(|| panic!("No generator for day {}", day));
+    print!("{input}");
+}
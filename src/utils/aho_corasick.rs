@@ -0,0 +1,215 @@
+use std::collections::VecDeque;
+
+// Marks a missing trie edge while building, every edge is filled in afterwards
+const NO_STATE: u32 = u32::MAX;
+
+/// One occurrence of a pattern, `start..end` are byte offsets into the stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PatternMatch {
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Aho–Corasick automaton finding every (overlapping) occurrence of any number
+/// of byte patterns in a single pass over a stream. Empty patterns never match.
+pub struct AhoCorasick {
+    // Full transition table, so each byte is one lookup
+    transitions: Vec<[u32; 256]>,
+    // Patterns ending at each state, including those ending at its suffixes
+    outputs: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl AhoCorasick {
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let mut automaton = Self {
+            transitions: vec![[NO_STATE; 256]],
+            outputs: vec![Vec::new()],
+            lengths: Vec::new(),
+        };
+
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            automaton.lengths.push(pattern.len());
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for &byte in pattern {
+                if automaton.transitions[state][byte as usize] == NO_STATE {
+                    automaton.transitions[state][byte as usize] =
+                        automaton.transitions.len() as u32;
+                    automaton.transitions.push([NO_STATE; 256]);
+                    automaton.outputs.push(Vec::new());
+                }
+                state = automaton.transitions[state][byte as usize] as usize;
+            }
+            automaton.outputs[state].push(id);
+        }
+
+        automaton.link();
+        automaton
+    }
+
+    // Breadth first, so a state's failure link is always finished before the state itself
+    fn link(&mut self) {
+        let mut fail = vec![0; self.transitions.len()];
+        let mut queue = VecDeque::new();
+
+        for byte in 0..256 {
+            match self.transitions[0][byte] {
+                NO_STATE => self.transitions[0][byte] = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let inherited = self.outputs[fail[state]].clone();
+            self.outputs[state].extend(inherited);
+
+            for byte in 0..256 {
+                let fallback = self.transitions[fail[state]][byte];
+                match self.transitions[state][byte] {
+                    NO_STATE => self.transitions[state][byte] = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn pattern_count(&self) -> usize {
+        self.lengths.len()
+    }
+
+    pub fn pattern_len(&self, pattern: usize) -> usize {
+        self.lengths[pattern]
+    }
+
+    /// Matches in order of their end offset, e.g. bytes from a grid row or ray
+    pub fn find_iter<I: IntoIterator<Item = u8>>(&self, stream: I) -> FindIter<'_, I::IntoIter> {
+        FindIter {
+            automaton: self,
+            stream: stream.into_iter(),
+            state: 0,
+            offset: 0,
+            pending: 0,
+        }
+    }
+
+    pub fn find_all(&self, haystack: &(impl AsRef<[u8]> + ?Sized)) -> Vec<PatternMatch> {
+        self.find_iter(haystack.as_ref().iter().copied()).collect()
+    }
+}
+
+pub struct FindIter<'a, I> {
+    automaton: &'a AhoCorasick,
+    stream: I,
+    state: usize,
+    // Bytes consumed so far, i.e. the end offset of matches in the current state
+    offset: usize,
+    // Outputs of the current state not yet returned
+    pending: usize,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for FindIter<'_, I> {
+    type Item = PatternMatch;
+
+    fn next(&mut self) -> Option<PatternMatch> {
+        let automaton = self.automaton;
+
+        while self.pending == 0 {
+            let byte = self.stream.next()?;
+            self.state = automaton.transitions[self.state][byte as usize] as usize;
+            self.offset += 1;
+            self.pending = automaton.outputs[self.state].len();
+        }
+
+        let outputs = &automaton.outputs[self.state];
+        let pattern = outputs[outputs.len() - self.pending];
+        self.pending -= 1;
+
+        Some(PatternMatch {
+            pattern,
+            start: self.offset - automaton.lengths[pattern],
+            end: self.offset,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_matches() {
+        let automaton = AhoCorasick::new(["he", "she", "his", "hers"]);
+        let found: Vec<(usize, usize)> = automaton
+            .find_all("ushers")
+            .iter()
+            .map(|m| (m.pattern, m.start))
+            .collect();
+        assert_eq!(found, [(1, 1), (0, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn test_repeated_pattern() {
+        let automaton = AhoCorasick::new(["aa"]);
+        assert_eq!(automaton.find_all("aaaa").len(), 3);
+    }
+
+    #[test]
+    fn test_duplicate_and_empty_patterns() {
+        let automaton = AhoCorasick::new(["ab", "", "ab"]);
+        let patterns: Vec<usize> = automaton
+            .find_all("xab")
+            .iter()
+            .map(|m| m.pattern)
+            .collect();
+        assert_eq!(patterns, [0, 2]);
+        assert_eq!(automaton.pattern_count(), 3);
+        assert_eq!(automaton.pattern_len(1), 0);
+    }
+
+    #[test]
+    fn test_offsets() {
+        let automaton = AhoCorasick::new(["MAS", "SAM"]);
+        assert_eq!(
+            automaton.find_all("SAMASAM"),
+            [
+                PatternMatch {
+                    pattern: 1,
+                    start: 0,
+                    end: 3
+                },
+                PatternMatch {
+                    pattern: 0,
+                    start: 2,
+                    end: 5
+                },
+                PatternMatch {
+                    pattern: 1,
+                    start: 4,
+                    end: 7
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_input() {
+        let automaton = AhoCorasick::new(["XMAS"]);
+        let stream = "X-M-A-S-".bytes().step_by(2);
+        assert_eq!(automaton.find_iter(stream).count(), 1);
+    }
+
+    #[test]
+    fn test_no_patterns() {
+        let automaton = AhoCorasick::new::<&str>([]);
+        assert!(automaton.find_all("anything").is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::utils::{
+    direction::{ALL_DIRECTIONS, CARDINAL_DIRECTIONS, Directions},
+    grid::Grid,
+    position::{Delta, Position},
+};
+
+/// Anything that maps positions to cells, so neighbour queries and the searches
+/// built on them work the same on a dense Grid and a SparseGrid
+pub trait Cells {
+    type Value;
+
+    // None when there's no cell at pos, off the grid or unoccupied
+    fn cell(&self, pos: Position) -> Option<&Self::Value>;
+
+    fn has_cell(&self, pos: Position) -> bool {
+        self.cell(pos).is_some()
+    }
+
+    /// Where a move by delta from pos lands, None when there's no cell there.
+    /// This is the addressing mode, e.g. a torus wraps instead.
+    fn offset(&self, pos: Position, delta: Delta) -> Option<Position> {
+        let next = checked_offset(pos, delta)?;
+        self.has_cell(next).then_some(next)
+    }
+
+    // The neighbouring position in a direction, None when there's no cell there
+    fn position_in_direction(&self, pos: Position, direction: Directions) -> Option<Position> {
+        self.offset(pos, direction.delta())
+    }
+
+    // Cells at the given offsets from pos, missing ones are skipped
+    fn neighbors_with<'a>(
+        &'a self,
+        pos: Position,
+        offsets: impl IntoIterator<Item = Delta> + 'a,
+    ) -> impl Iterator<Item = (Position, &'a Self::Value)> + 'a {
+        offsets.into_iter().filter_map(move |delta| {
+            let next = self.offset(pos, delta)?;
+            Some((next, self.cell(next)?))
+        })
+    }
+
+    // Orthogonal neighbours, clockwise from Top
+    fn neighbors4(&self, pos: Position) -> impl Iterator<Item = (Position, &Self::Value)> {
+        self.neighbors_with(pos, CARDINAL_DIRECTIONS.map(Directions::delta))
+    }
+
+    // Orthogonal and diagonal neighbours, clockwise from Top
+    fn neighbors8(&self, pos: Position) -> impl Iterator<Item = (Position, &Self::Value)> {
+        self.neighbors_with(pos, ALL_DIRECTIONS.map(Directions::delta))
+    }
+
+    /// Fixed-size neighbourhood in the order the directions are given, with None
+    /// for missing cells so callers can destructure the result directly
+    fn neighborhood<const N: usize>(
+        &self,
+        pos: Position,
+        directions: [Directions; N],
+    ) -> [Option<(Position, &Self::Value)>; N] {
+        directions.map(|direction| {
+            let next = self.position_in_direction(pos, direction)?;
+            Some((next, self.cell(next)?))
+        })
+    }
+}
+
+// pos + delta, positions near isize::MAX must not overflow
+fn checked_offset(pos: Position, delta: Delta) -> Option<Position> {
+    Some(Position::new(
+        pos.row.checked_add(delta.row)?,
+        pos.col.checked_add(delta.col)?,
+    ))
+}
+
+impl<T> Cells for Grid<T> {
+    type Value = T;
+
+    fn cell(&self, pos: Position) -> Option<&T> {
+        self.get_at(pos)
+    }
+}
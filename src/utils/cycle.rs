@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// States from step `start` on repeat every `length` steps, step 0 being the initial state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // The earliest step whose state equals the state at step n
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Every state up to the first repeat, as found by find_cycle
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    // The state at any step, however far past the end of the recorded history
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hash-based detection: steps until a state repeats, keeping each state once
+/// and indexing it by a 64-bit fingerprint. None if no state repeats within
+/// max_steps steps.
+pub fn find_cycle<S: Hash + Eq>(
+    initial: S,
+    max_steps: Option<usize>,
+    step: impl FnMut(&S) -> S,
+) -> Option<History<S>> {
+    let (states, cycle) = record(initial, max_steps, step);
+    Some(History {
+        cycle: cycle?,
+        states,
+    })
+}
+
+/// The state after n steps, stopping early and extrapolating once states repeat
+pub fn state_at<S: Hash + Eq>(initial: S, n: usize, step: impl FnMut(&S) -> S) -> S {
+    let (mut states, cycle) = record(initial, Some(n), step);
+    let index = cycle.map_or(n, |cycle| cycle.equivalent_step(n));
+    states.swap_remove(index)
+}
+
+// States from step 0 until the first repeat, or until step max_steps when nothing repeats by then
+fn record<S: Hash + Eq>(
+    initial: S,
+    max_steps: Option<usize>,
+    mut step: impl FnMut(&S) -> S,
+) -> (Vec<S>, Option<Cycle>) {
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        let key = fingerprint(&state);
+        // Fingerprints can collide, so candidates are compared in full
+        if let Some(&start) = seen
+            .get(&key)
+            .and_then(|steps| steps.iter().find(|&&i| states[i] == state))
+        {
+            let length = states.len() - start;
+            return (states, Some(Cycle { start, length }));
+        }
+
+        seen.entry(key).or_default().push(states.len());
+        states.push(state);
+        if max_steps.is_some_and(|max| states.len() > max) {
+            return (states, None);
+        }
+        state = step(&states[states.len() - 1]);
+    }
+}
+
+/// Floyd's tortoise and hare, holding two states at a time instead of the whole
+/// history. Steps the simulation about three times as often as find_cycle.
+pub fn floyd<S: PartialEq>(
+    initial: S,
+    max_steps: Option<usize>,
+    mut step: impl FnMut(&S) -> S,
+) -> Option<Cycle> {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    let mut steps = 1;
+    while tortoise != hare {
+        if max_steps.is_some_and(|max| steps > max) {
+            return None;
+        }
+        tortoise = step(&tortoise);
+        let ahead = step(&hare);
+        hare = step(&ahead);
+        steps += 1;
+    }
+
+    // The hare is now a multiple of the cycle length ahead, so walking both in
+    // step from the start and the meeting point they first agree at the cycle start
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+/// Brent's algorithm, constant memory like floyd but with fewer steps
+pub fn brent<S: PartialEq + Clone>(
+    initial: S,
+    max_steps: Option<usize>,
+    mut step: impl FnMut(&S) -> S,
+) -> Option<Cycle> {
+    // Find the length by letting the hare run ahead of a tortoise that teleports at powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if max_steps.is_some_and(|max| steps > max) {
+            return None;
+        }
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // With the hare a whole cycle ahead, they first meet at the cycle start
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Some(Cycle { start, length })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    // 0, 1, ..., 9 and then back to 4
+    fn rho(x: &u32) -> u32 {
+        if *x < 9 { x + 1 } else { 4 }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        let expected = Cycle {
+            start: 4,
+            length: 6,
+        };
+        assert_eq!(find_cycle(0, None, rho).unwrap().cycle, expected);
+        assert_eq!(floyd(0, None, rho), Some(expected));
+        assert_eq!(brent(0, None, rho), Some(expected));
+
+        let lcg = |x: &u64| (x * x + 1) % 255;
+        let hashed = find_cycle(3, None, lcg).unwrap().cycle;
+        assert_eq!(floyd(3, None, lcg), Some(hashed));
+        assert_eq!(brent(3, None, lcg), Some(hashed));
+    }
+
+    #[test]
+    fn test_cycle_from_the_start() {
+        let flip = |x: &bool| !x;
+        let expected = Some(Cycle {
+            start: 0,
+            length: 2,
+        });
+        assert_eq!(floyd(true, None, flip), expected);
+        assert_eq!(brent(true, None, flip), expected);
+        assert_eq!(find_cycle(true, None, flip).map(|h| h.cycle), expected);
+    }
+
+    #[test]
+    fn test_state_at_far_step() {
+        // Step 1_000_000_001 is 999_999_997 steps into the cycle, 1 past a whole number of laps
+        assert_eq!(state_at(0, 1_000_000_001, rho), 5);
+        assert_eq!(state_at(0, 3, rho), 3);
+
+        let cycle = brent(0, None, rho).unwrap();
+        assert_eq!(cycle.equivalent_step(10), 4);
+        assert_eq!(cycle.equivalent_step(2), 2);
+    }
+
+    #[test]
+    fn test_max_steps() {
+        let count = |x: &u64| x + 1;
+        assert!(find_cycle(0, Some(100), count).is_none());
+        assert_eq!(floyd(0, Some(100), count), None);
+        assert_eq!(brent(0, Some(100), count), None);
+    }
+
+    #[test]
+    fn test_grid_states() {
+        let grid = Grid::new("ab\ncd");
+        let history = find_cycle(grid.clone(), None, |g| g.rotate_90().unwrap()).unwrap();
+        assert_eq!(
+            history.cycle,
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(history.state_at(1_000_000_002), &grid.rotate_180().unwrap());
+        assert_eq!(
+            brent(grid, None, |g| g.rotate_90().unwrap()),
+            Some(history.cycle)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::utils::position::Delta;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Directions {
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+    TopLeft,
+}
+
+// Clockwise from Top, turning relies on this order
+pub const ALL_DIRECTIONS: [Directions; 8] = [
+    Directions::Top,
+    Directions::TopRight,
+    Directions::Right,
+    Directions::BottomRight,
+    Directions::Bottom,
+    Directions::BottomLeft,
+    Directions::Left,
+    Directions::TopLeft,
+];
+
+pub const CARDINAL_DIRECTIONS: [Directions; 4] = [
+    Directions::Top,
+    Directions::Right,
+    Directions::Bottom,
+    Directions::Left,
+];
+
+pub const DIAGONAL_DIRECTIONS: [Directions; 4] = [
+    Directions::TopRight,
+    Directions::BottomRight,
+    Directions::BottomLeft,
+    Directions::TopLeft,
+];
+
+impl Directions {
+    // Position in ALL_DIRECTIONS, i.e. the number of 45° clockwise turns from Top
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn from_index(index: usize) -> Self {
+        ALL_DIRECTIONS[index % ALL_DIRECTIONS.len()]
+    }
+
+    pub fn delta(self) -> Delta {
+        // Map each direction to its row/col offset
+        let (row, col) = match self {
+            Directions::Top => (-1, 0),
+            Directions::TopRight => (-1, 1),
+            Directions::Right => (0, 1),
+            Directions::BottomRight => (1, 1),
+            Directions::Bottom => (1, 0),
+            Directions::BottomLeft => (1, -1),
+            Directions::Left => (0, -1),
+            Directions::TopLeft => (-1, -1),
+        };
+        Delta::new(row, col)
+    }
+
+    // The direction a single step of delta goes, None for any other delta
+    pub fn from_delta(delta: Delta) -> Option<Self> {
+        ALL_DIRECTIONS
+            .into_iter()
+            .find(|direction| direction.delta() == delta)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    // 90° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    // 90° counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    pub fn turn_right_45(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left_45(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// Reads the headings puzzles use for orthogonal movement:
+    /// arrows `^>v<`, `U/R/D/L` and compass `N/E/S/W`, in either case
+    pub fn from_char(c: char) -> Option<Self> {
+        match c.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Some(Directions::Top),
+            '>' | 'R' | 'E' => Some(Directions::Right),
+            'V' | 'D' | 'S' => Some(Directions::Bottom),
+            '<' | 'L' | 'W' => Some(Directions::Left),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Directions::Top.turn_right(), Directions::Right);
+        assert_eq!(Directions::Top.turn_left(), Directions::Left);
+        assert_eq!(Directions::TopLeft.turn_right_45(), Directions::Top);
+        assert_eq!(Directions::Top.turn_left_45(), Directions::TopLeft);
+        assert_eq!(Directions::BottomLeft.turn_right(), Directions::TopLeft);
+    }
+
+    #[test]
+    fn test_four_turns_is_identity() {
+        for direction in ALL_DIRECTIONS {
+            let turned = (0..4).fold(direction, |d, _| d.turn_right());
+            assert_eq!(turned, direction);
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+    }
+
+    #[test]
+    fn test_opposite() {
+        for direction in ALL_DIRECTIONS {
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(direction.opposite().opposite(), direction);
+        }
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(Directions::Top.delta(), Delta::new(-1, 0));
+        assert_eq!(Directions::BottomLeft.delta(), Delta::new(1, -1));
+    }
+
+    #[test]
+    fn test_from_delta() {
+        for direction in ALL_DIRECTIONS {
+            assert_eq!(Directions::from_delta(direction.delta()), Some(direction));
+        }
+        assert_eq!(Directions::from_delta(Delta::new(0, 2)), None);
+        assert_eq!(Directions::from_delta(Delta::new(0, 0)), None);
+    }
+
+    #[test]
+    fn test_subsets() {
+        assert!(CARDINAL_DIRECTIONS.iter().all(|d| !d.is_diagonal()));
+        assert!(DIAGONAL_DIRECTIONS.iter().all(|d| d.is_diagonal()));
+        assert!(
+            CARDINAL_DIRECTIONS
+                .iter()
+                .all(|d| d.delta().manhattan() == 1)
+        );
+    }
+
+    #[test]
+    fn test_from_char() {
+        for (chars, expected) in [
+            ("^UuNn", Directions::Top),
+            (">RrEe", Directions::Right),
+            ("vVDdSs", Directions::Bottom),
+            ("<LlWw", Directions::Left),
+        ] {
+            for c in chars.chars() {
+                assert_eq!(Directions::from_char(c), Some(expected));
+            }
+        }
+        assert_eq!(Directions::from_char('x'), None);
+    }
+}
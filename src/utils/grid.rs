@@ -1,38 +1,68 @@
-use core::fmt;
-use std::{fmt::Display, str::from_utf8};
-
-#[derive(Clone, Copy, Debug)]
-pub enum Directions {
-    Top,
-    TopRight,
-    Right,
-    BottomRight,
-    Bottom,
-    BottomLeft,
-    Left,
-    TopLeft,
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::iter::{StepBy, Take};
+use std::slice;
+
+use crate::utils::{parse::ParseError, position::Position};
+
+/// Why text can't be read as a rectangular byte grid. Lines are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GridError {
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    BlankLine {
+        line: usize,
+    },
+    NonAscii {
+        line: usize,
+        col: usize,
+    },
 }
 
-pub const ALL_DIRECTIONS: [Directions; 8] = [
-    Directions::Top,
-    Directions::TopRight,
-    Directions::Right,
-    Directions::BottomRight,
-    Directions::Bottom,
-    Directions::BottomLeft,
-    Directions::Left,
-    Directions::TopLeft,
-];
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Point {
-    pub row: usize,
-    pub col: usize,
-    pub value: u8,
+impl GridError {
+    pub fn line(&self) -> usize {
+        match self {
+            GridError::RaggedRow { line, .. }
+            | GridError::BlankLine { line }
+            | GridError::NonAscii { line, .. } => *line,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            GridError::RaggedRow {
+                expected, found, ..
+            } => format!("row is {found} wide, expected {expected}"),
+            GridError::BlankLine { .. } => "blank line inside grid".to_owned(),
+            GridError::NonAscii { col, .. } => format!("non-ASCII byte at column {col}"),
+        }
+    }
 }
 
-pub struct Grid {
-    data: Vec<u8>,
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line(), self.message())
+    }
+}
+
+impl Error for GridError {}
+
+impl From<GridError> for ParseError {
+    fn from(err: GridError) -> Self {
+        ParseError::new(err.line(), err.message())
+    }
+}
+
+/// Cells of a column or diagonal, walking the row-major data with a fixed stride
+pub type Strided<'a, T> = Take<StepBy<slice::Iter<'a, T>>>;
+
+#[derive(Clone, Debug)]
+pub struct Grid<T = u8> {
+    data: Vec<T>,
     row_size: usize,
 }
 
@@ -57,86 +87,389 @@ impl Grid {
         Self { data, row_size }
     }
 
-    // get when row size is constant
-    pub fn get(&self, row: usize, col: usize) -> Option<&u8> {
-        let row_offset = self.row_size * row;
+    /// Validating version of new: every row must be the same width, blank (or
+    /// whitespace-only) lines are only allowed at the end, and every byte must be
+    /// ASCII so cells are characters
+    pub fn parse(input: &str) -> Result<Self, GridError> {
+        Self::parse_rows(input, None)
+    }
 
-        // Check for out of bounds
-        if col >= self.row_size {
-            return None;
+    // Lenient version of parse that pads short rows out to the widest row with fill
+    pub fn parse_padded(input: &str, fill: u8) -> Result<Self, GridError> {
+        Self::parse_rows(input, Some(fill))
+    }
+
+    fn parse_rows(input: &str, fill: Option<u8>) -> Result<Self, GridError> {
+        let mut rows: Vec<&str> = input.lines().collect();
+        while rows.last().is_some_and(|row| row.trim().is_empty()) {
+            rows.pop();
         }
-        if row_offset + col >= self.data.len() {
-            return None;
+
+        for (index, row) in rows.iter().enumerate() {
+            if row.trim().is_empty() {
+                return Err(GridError::BlankLine { line: index + 1 });
+            }
+            if let Some(col) = row.bytes().position(|b| !b.is_ascii()) {
+                return Err(GridError::NonAscii {
+                    line: index + 1,
+                    col: col + 1,
+                });
+            }
         }
 
-        Some(&self.data[row_offset + col])
+        let row_size = match fill {
+            Some(_) => rows.iter().map(|row| row.len()).max().unwrap_or(0),
+            None => rows.first().map_or(0, |row| row.len()),
+        };
+
+        let mut data = Vec::with_capacity(row_size * rows.len());
+        for (index, row) in rows.iter().enumerate() {
+            match fill {
+                Some(fill) => {
+                    data.extend_from_slice(row.as_bytes());
+                    data.resize(data.len() + row_size - row.len(), fill);
+                }
+                None if row.len() != row_size => {
+                    return Err(GridError::RaggedRow {
+                        line: index + 1,
+                        expected: row_size,
+                        found: row.len(),
+                    });
+                }
+                None => data.extend_from_slice(row.as_bytes()),
+            }
+        }
+
+        Ok(Self { data, row_size })
     }
 
     pub fn _as_bytes(&self) -> &[u8] {
         &self.data
     }
+}
+
+impl<T> Grid<T> {
+    // Same layout rules as new, but each character is mapped to a cell
+    pub fn from_chars(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut data = Vec::with_capacity(input.len());
+        let mut row_size = 0;
 
-    pub fn _iter_rows(&self) -> impl Iterator<Item = &[u8]> {
-        self.data.chunks(self.row_size)
+        for line in input.lines() {
+            let len_before = data.len();
+            data.extend(line.chars().map(&mut f));
+
+            if row_size == 0 {
+                row_size = data.len() - len_before;
+            }
+        }
+
+        Self { data, row_size }
     }
 
-    pub fn iter_2d(&self) -> impl Iterator<Item = Point> {
-        self.data.iter().enumerate().map(move |(i, value)| {
-            let row = i / self.row_size;
-            let col = i % self.row_size;
-            Point {
-                row,
-                col,
-                value: *value,
+    // Like from_chars, for mappings that can reject a character, e.g. char::to_digit
+    pub fn try_from_chars(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut data = Vec::with_capacity(input.len());
+        let mut row_size = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            let len_before = data.len();
+            for c in line.chars() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::new(index + 1, format!("unexpected grid character {c:?}"))
+                })?;
+                data.push(cell);
             }
-        })
+
+            if row_size == 0 {
+                row_size = data.len() - len_before;
+            }
+        }
+
+        Ok(Self { data, row_size })
     }
 
-    pub fn get_point_in_direction(&self, point: &Point, direction: &Directions) -> Option<Point> {
-        // Map each direction to its row/col offset
-        let (dr, dc) = match direction {
-            Directions::Top => (-1, 0),
-            Directions::TopRight => (-1, 1),
-            Directions::Right => (0, 1),
-            Directions::BottomRight => (1, 1),
-            Directions::Bottom => (1, 0),
-            Directions::BottomLeft => (1, -1),
-            Directions::Left => (0, -1),
-            Directions::TopLeft => (-1, -1),
-        };
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        // A zero width grid has no cells, whatever the height
+        let len = if width == 0 { 0 } else { width * height };
+        Self {
+            data: vec![value; len],
+            row_size: width,
+        }
+    }
 
-        // Use checked_add/sub to avoid negative indices
-        let new_row = (point.row as isize).checked_add(dr)?;
-        let new_col = (point.col as isize).checked_add(dc)?;
+    // Builds a width x height grid by calling f with each position in row-major order
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        let height = if width == 0 { 0 } else { height };
+        let mut data = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                data.push(f(Position::new(row as isize, col as isize)));
+            }
+        }
+        Self {
+            data,
+            row_size: width,
+        }
+    }
 
-        // Guard against negative
-        if new_row < 0 || new_col < 0 {
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            row_size: self.row_size,
+        }
+    }
+
+    /// Number of columns, i.e. the configured row size, so filled(5, 0, ..) is 5
+    /// wide with no rows. height, is_empty, in_bounds, index_of and iter_2d are
+    /// safe on any grid, empty or not.
+    pub fn width(&self) -> usize {
+        self.row_size
+    }
+
+    // Grids from new can be ragged, in which case the last row is only partly filled
+    pub fn height(&self) -> usize {
+        if self.row_size == 0 {
+            0
+        } else {
+            self.data.len().div_ceil(self.row_size)
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn in_bounds(&self, row: usize, col: usize) -> bool {
+        self.index_of(row, col).is_some()
+    }
+
+    // Flat index into the row-major data, None when out of bounds
+    pub fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        if col >= self.row_size {
             return None;
         }
 
-        let new_row = new_row as usize;
-        let new_col = new_col as usize;
+        // Huge rows from untrusted coordinates must not overflow the index
+        let index = self.row_size.checked_mul(row)?.checked_add(col)?;
+        (index < self.data.len()).then_some(index)
+    }
 
-        // Use get and map the result
-        self.get(new_row, new_col).map(|val| Point {
-            row: new_row,
-            col: new_col,
-            value: *val,
+    // (row, col) of a flat index, None when out of bounds
+    pub fn position_of(&self, index: usize) -> Option<(usize, usize)> {
+        (index < self.data.len()).then(|| (index / self.row_size, index % self.row_size))
+    }
+
+    // Signed lookup for computed coordinates, negatives are simply out of bounds
+    pub fn get_signed(&self, row: isize, col: isize) -> Option<&T> {
+        let row = usize::try_from(row).ok()?;
+        let col = usize::try_from(col).ok()?;
+        self.get(row, col)
+    }
+
+    // get when row size is constant
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|index| &self.data[index])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col).map(|index| &mut self.data[index])
+    }
+
+    // Returns the previous value, or None (leaving the grid untouched) when out of bounds
+    pub fn set(&mut self, row: usize, col: usize, value: T) -> Option<T> {
+        self.get_mut(row, col)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    // Returns false without swapping when either cell is out of bounds
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) -> bool {
+        match (self.index_of(a.0, a.1), self.index_of(b.0, b.1)) {
+            (Some(a), Some(b)) => {
+                self.data.swap(a, b);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> Option<&mut [T]> {
+        let start = self.index_of(row, 0)?;
+        // The last row of a ragged grid from new can be short
+        let end = (start + self.row_size).min(self.data.len());
+        Some(&mut self.data[start..end])
+    }
+
+    // Columns aren't contiguous, so this walks the data with a stride of one row
+    pub fn col_mut(&mut self, col: usize) -> impl Iterator<Item = &mut T> {
+        let (skip, step) = if col < self.row_size {
+            (col, self.row_size)
+        } else {
+            (self.data.len(), 1)
+        };
+        self.data.iter_mut().skip(skip).step_by(step)
+    }
+
+    /// Writes one simulation step into next, computing each cell from this grid with
+    /// rule(grid, position). Returns whether any cell changed.
+    pub fn step_into(
+        &self,
+        next: &mut Grid<T>,
+        mut rule: impl FnMut(&Grid<T>, Position) -> T,
+    ) -> bool
+    where
+        T: PartialEq,
+    {
+        // Reuse next's allocation when it's already the right shape
+        if next.data.len() != self.data.len() {
+            next.data.clear();
+            next.data.reserve(self.data.len());
+        }
+        next.row_size = self.row_size;
+
+        let mut changed = false;
+        for i in 0..self.data.len() {
+            let value = rule(self, self.position_at_index(i));
+            changed |= value != self.data[i];
+
+            if i < next.data.len() {
+                next.data[i] = value;
+            } else {
+                next.data.push(value);
+            }
+        }
+
+        changed
+    }
+
+    // Grids never hold more than isize::MAX cells, so the casts can't wrap
+    fn position_at_index(&self, index: usize) -> Position {
+        Position::new(
+            (index / self.row_size) as isize,
+            (index % self.row_size) as isize,
+        )
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.get_at(pos).is_some()
+    }
+
+    pub fn get_at(&self, pos: Position) -> Option<&T> {
+        self.get_signed(pos.row, pos.col)
+    }
+
+    pub fn get_at_mut(&mut self, pos: Position) -> Option<&mut T> {
+        let row = usize::try_from(pos.row).ok()?;
+        let col = usize::try_from(pos.col).ok()?;
+        self.get_mut(row, col)
+    }
+
+    pub fn set_at(&mut self, pos: Position, value: T) -> Option<T> {
+        self.get_at_mut(pos)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a zero size, which only happens for an empty grid
+        self.data.chunks(self.row_size.max(1))
+    }
+
+    // The last row of a ragged grid is shorter than the others
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        let start = self.index_of(row, 0)?;
+        let end = (start + self.row_size).min(self.data.len());
+        Some(&self.data[start..end])
+    }
+
+    // At most len cells from the flat index start, stride apart, stopping at the end of the data
+    fn strided(&self, start: usize, stride: usize, len: usize) -> Strided<'_, T> {
+        let data = self.data.get(start..).unwrap_or_default();
+        data.iter().step_by(stride.max(1)).take(len)
+    }
+
+    // Top to bottom, empty when col is out of bounds
+    pub fn column(&self, col: usize) -> Strided<'_, T> {
+        let len = if col < self.row_size {
+            self.height()
+        } else {
+            0
+        };
+        self.strided(col, self.row_size, len)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Strided<'_, T>> {
+        (0..self.row_size).map(|col| self.column(col))
+    }
+
+    /// Lines running down and to the right, starting from each cell of the top
+    /// row and then each cell of the left column below it
+    pub fn diagonals(&self) -> impl Iterator<Item = Strided<'_, T>> {
+        let (width, height) = (self.row_size, self.height());
+        let starts = (0..width).map(|col| (0, col));
+        let starts = starts.chain((1..height).map(|row| (row, 0)));
+
+        starts.map(move |(row, col)| {
+            let len = (height - row).min(width - col);
+            self.strided(row * width + col, width + 1, len)
         })
     }
+
+    /// Lines running down and to the left, starting from each cell of the top
+    /// row and then each cell of the right column below it. The short last row
+    /// of a ragged grid has no cell in the right column, so no line starts there.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Strided<'_, T>> {
+        let (width, height) = (self.row_size, self.height());
+        let starts = (0..width).map(|col| (0, col));
+        let starts = starts.chain((1..height).map(move |row| (row, width - 1)));
+
+        let starts = starts.filter(|&(row, col)| self.get(row, col).is_some());
+        starts.map(move |(row, col)| {
+            let len = (height - row).min(col + 1);
+            self.strided(row * width + col, width - 1, len)
+        })
+    }
+
+    pub fn iter_2d(&self) -> impl Iterator<Item = (Position, &T)> {
+        // Only a grid with no data has a zero width, so position_at_index never divides by zero
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| (self.position_at_index(i), value))
+    }
 }
 
-impl Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut i = 0;
-        let data = &self.data;
+// Same cells in the same shape, all empty grids are equal whatever their width
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return self.is_empty() && other.is_empty();
+        }
+        self.row_size == other.row_size && self.data == other.data
+    }
+}
 
-        while i < data.len() {
-            let line_bytes = &data[i..i + self.row_size];
-            i += self.row_size;
+impl<T: Eq> Eq for Grid<T> {}
 
-            let line = from_utf8(line_bytes).map_err(|_| fmt::Error)?;
-            writeln!(f, "{line}")?;
+// Matches eq, the width only counts when there are cells. Byte grids hash as a single slice.
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if !self.is_empty() {
+            self.row_size.hash(state);
+        }
+        self.data.hash(state);
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Rows can split multi-byte characters, so decode lossily rather than erroring
+        for line_bytes in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(line_bytes))?;
         }
 
         Ok(())
@@ -146,6 +479,7 @@ impl Display for Grid {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{cells::Cells, direction::Directions, position::Delta};
 
     #[test]
     fn test_single_line_input() {
@@ -213,6 +547,13 @@ mod tests {
         assert_eq!(grid._as_bytes(), &expected);
     }
 
+    #[test]
+    fn test_display_ragged_rows() {
+        let input = "ab\nc";
+        let grid = Grid::new(input);
+        assert_eq!(format!("{grid}"), "ab\nc\n");
+    }
+
     #[test]
     fn test_normal_day_input_example() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
@@ -225,6 +566,393 @@ mod tests {
         assert_eq!(grid._as_bytes(), &expected);
     }
 
+    #[test]
+    fn test_parse_rectangular() {
+        let grid = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!(grid._as_bytes(), b"abcdef");
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let grid = Grid::parse("abc\r\ndef\r\n\r\n").unwrap();
+        assert_eq!(grid._as_bytes(), b"abcdef");
+    }
+
+    #[test]
+    fn test_parse_whitespace_trailing_lines() {
+        let grid = Grid::parse("abc\n   \n\t\n").unwrap();
+        assert_eq!(grid._as_bytes(), b"abc");
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        let grid = Grid::parse("").unwrap();
+        assert!(grid._as_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_parse_ragged_row() {
+        let err = Grid::parse("abc\nde\nfgh").unwrap_err();
+        assert_eq!(
+            err,
+            GridError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(format!("{err}"), "line 2: row is 2 wide, expected 3");
+    }
+
+    #[test]
+    fn test_parse_interior_blank_line() {
+        let err = Grid::parse("one\n\ntwo").unwrap_err();
+        assert_eq!(err, GridError::BlankLine { line: 2 });
+    }
+
+    #[test]
+    fn test_parse_interior_whitespace_line() {
+        let err = Grid::parse("abc\n   \nabc").unwrap_err();
+        assert_eq!(err, GridError::BlankLine { line: 2 });
+        let err = Grid::parse_padded("ab\n \nabc", b'.').unwrap_err();
+        assert_eq!(err, GridError::BlankLine { line: 2 });
+    }
+
+    #[test]
+    fn test_parse_nonascii() {
+        let err = Grid::parse("hello\nhéllo").unwrap_err();
+        assert_eq!(err, GridError::NonAscii { line: 2, col: 2 });
+    }
+
+    #[test]
+    fn test_parse_error_into_parse_error() {
+        let err: ParseError = Grid::parse("abc\nde").unwrap_err().into();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "row is 2 wide, expected 3");
+    }
+
+    #[test]
+    fn test_parse_padded() {
+        let grid = Grid::parse_padded("ab\nabcd\na", b'.').unwrap();
+        assert_eq!(grid._as_bytes(), b"ab..abcda...");
+        assert_eq!(grid.get(2, 3), Some(&b'.'));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let grid = Grid::new("abc\ndef");
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert!(!grid.is_empty());
+        assert!(grid.in_bounds(1, 2));
+        assert!(!grid.in_bounds(2, 0));
+        assert!(!grid.in_bounds(0, 3));
+    }
+
+    #[test]
+    fn test_empty_grid_dimensions() {
+        for grid in [
+            Grid::new(""),
+            Grid::filled(0, 5, b'.'),
+            Grid::filled(5, 0, b'.'),
+        ] {
+            assert_eq!(grid.height(), 0);
+            assert!(grid.is_empty());
+            assert!(!grid.in_bounds(0, 0));
+            assert_eq!(grid.index_of(0, 0), None);
+            assert_eq!(grid.position_of(0), None);
+            assert_eq!(grid.iter_2d().count(), 0);
+            assert_eq!(grid.rows().count(), 0);
+        }
+        assert_eq!(Grid::filled(5, 0, b'.').width(), 5);
+    }
+
+    #[test]
+    fn test_ragged_grid_dimensions() {
+        let grid = Grid::new("ab\nc");
+        assert_eq!(grid.height(), 2);
+        assert!(grid.in_bounds(1, 0));
+        assert!(!grid.in_bounds(1, 1));
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        let grid = Grid::new("abc\ndef");
+        assert_eq!(grid.index_of(1, 1), Some(4));
+        assert_eq!(grid.position_of(4), Some((1, 1)));
+        assert_eq!(grid.index_of(usize::MAX, 0), None);
+        assert_eq!(grid.position_of(6), None);
+        for i in 0..6 {
+            let (row, col) = grid.position_of(i).unwrap();
+            assert_eq!(grid.index_of(row, col), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_get_signed() {
+        let grid = Grid::new("abc\ndef");
+        assert_eq!(grid.get_signed(1, 2), Some(&b'f'));
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+        assert_eq!(grid.get_signed(isize::MIN, isize::MAX), None);
+    }
+
+    #[test]
+    fn test_position_lookups() {
+        let mut grid = Grid::new("abc\ndef");
+        assert_eq!(grid.get_at(Position::new(1, 2)), Some(&b'f'));
+        assert_eq!(grid.get_at(Position::new(-1, 0)), None);
+        assert!(grid.contains(Position::new(0, 0)));
+        assert!(!grid.contains(Position::new(2, 0)));
+        assert_eq!(grid.set_at(Position::new(0, 1), b'x'), Some(b'b'));
+        *grid.get_at_mut(Position::new(1, 0)).unwrap() = b'y';
+        assert_eq!(grid._as_bytes(), b"axcyef");
+    }
+
+    #[test]
+    fn test_iter_2d_positions() {
+        let grid = Grid::new("ab\ncd");
+        let cells: Vec<(Position, &u8)> = grid.iter_2d().collect();
+        assert_eq!(cells[0], (Position::new(0, 0), &b'a'));
+        assert_eq!(cells[3], (Position::new(1, 1), &b'd'));
+    }
+
+    #[test]
+    fn test_neighbors4() {
+        let grid = Grid::new("abc\ndef\nghi");
+        let center: Vec<u8> = grid
+            .neighbors4(Position::new(1, 1))
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(center, b"bfhd");
+
+        let corner: Vec<(Position, &u8)> = grid.neighbors4(Position::ORIGIN).collect();
+        assert_eq!(
+            corner,
+            [(Position::new(0, 1), &b'b'), (Position::new(1, 0), &b'd')]
+        );
+    }
+
+    #[test]
+    fn test_neighbors8() {
+        let grid = Grid::new("abc\ndef\nghi");
+        let center: Vec<u8> = grid
+            .neighbors8(Position::new(1, 1))
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(center, b"bcfihgda");
+        assert_eq!(grid.neighbors8(Position::new(2, 2)).count(), 3);
+        assert_eq!(grid.neighbors8(Position::new(-5, -5)).count(), 0);
+    }
+
+    #[test]
+    fn test_neighbors_with_offsets() {
+        // Knight moves
+        let grid = Grid::new("abc\ndef\nghi");
+        let offsets = [Delta::new(1, 2), Delta::new(2, 1), Delta::new(-1, 2)];
+        let found: Vec<u8> = grid
+            .neighbors_with(Position::ORIGIN, offsets)
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(found, b"fh");
+    }
+
+    #[test]
+    fn test_neighborhood_placeholders() {
+        let grid = Grid::new("abc\ndef\nghi");
+        let [top, right, bottom] = grid
+            .neighborhood(
+                Position::new(0, 2),
+                [Directions::Top, Directions::Right, Directions::Bottom],
+            )
+            .map(|cell| cell.map(|(_, v)| *v));
+        assert_eq!((top, right, bottom), (None, None, Some(b'f')));
+    }
+
+    #[test]
+    fn test_neighbors_near_isize_max() {
+        let grid = Grid::new("abc");
+        assert_eq!(
+            grid.neighbors8(Position::new(isize::MAX, isize::MAX))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_set_and_get_mut() {
+        let mut grid = Grid::new("abc\ndef");
+        assert_eq!(grid.set(1, 2, b'x'), Some(b'f'));
+        assert_eq!(grid.set(2, 0, b'x'), None);
+        *grid.get_mut(0, 0).unwrap() = b'z';
+        assert_eq!(grid._as_bytes(), b"zbcdex");
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = Grid::new("abc\ndef");
+        assert!(grid.swap((0, 0), (1, 2)));
+        assert!(!grid.swap((0, 0), (0, 3)));
+        assert_eq!(grid._as_bytes(), b"fbcdea");
+    }
+
+    #[test]
+    fn test_row_and_col_mut() {
+        let mut grid = Grid::new("abc\ndef\nghi");
+        grid.row_mut(1).unwrap().fill(b'.');
+        for cell in grid.col_mut(2) {
+            *cell = b'#';
+        }
+        assert_eq!(grid._as_bytes(), b"ab#..#gh#");
+        assert!(grid.row_mut(3).is_none());
+        assert_eq!(grid.col_mut(3).count(), 0);
+    }
+
+    #[test]
+    fn test_row_mut_short_last_row() {
+        let mut grid = Grid::new("abc\nd");
+        assert_eq!(grid.row_mut(1), Some(&mut b"d".to_vec()[..]));
+        grid.row_mut(1).unwrap().fill(b'.');
+        assert_eq!(grid._as_bytes(), b"abc.");
+    }
+
+    #[test]
+    fn test_step_into() {
+        // Every cell becomes true if its left neighbour was true
+        let grid = Grid::from_chars("#..\n.#.", |c| c == '#');
+        let mut next = Grid::filled(0, 0, false);
+        let changed = grid.step_into(&mut next, |prev, pos| {
+            *prev.get_at(pos).unwrap() || prev.get_at(pos + Delta::new(0, -1)) == Some(&true)
+        });
+        assert!(changed);
+        assert_eq!(next.get(0, 1), Some(&true));
+        assert_eq!(next.get(1, 2), Some(&true));
+        assert_eq!(next.get(0, 2), Some(&false));
+    }
+
+    #[test]
+    fn test_from_chars_digits() {
+        let input = "012\n345\n";
+        let grid = Grid::from_chars(input, |c| c.to_digit(10).unwrap());
+        assert_eq!(grid.get(1, 2), Some(&5));
+        assert_eq!(grid.get(0, 0), Some(&0));
+    }
+
+    #[test]
+    fn test_from_chars_enum() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        enum Tile {
+            Wall,
+            Floor,
+        }
+
+        let input = "#.\n.#";
+        let grid = Grid::from_chars(input, |c| if c == '#' { Tile::Wall } else { Tile::Floor });
+        assert_eq!(grid.get(0, 1), Some(&Tile::Floor));
+        assert_eq!(grid.get(1, 1), Some(&Tile::Wall));
+    }
+
+    #[test]
+    fn test_try_from_chars_rejects_character() {
+        let input = "012\n3x5";
+        let err = Grid::try_from_chars(input, |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "unexpected grid character 'x'");
+    }
+
+    #[test]
+    fn test_filled() {
+        let grid = Grid::filled(3, 2, false);
+        assert_eq!(grid.get(1, 2), Some(&false));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.iter_2d().count(), 6);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::new("abc\ndef\ng");
+        let rows: Vec<&[u8]> = grid.rows().collect();
+        assert_eq!(rows, [b"abc".as_slice(), b"def", b"g"]);
+        assert_eq!(grid.row(2), Some(b"g".as_slice()));
+        assert_eq!(grid.row(3), None);
+
+        let columns: Vec<Vec<u8>> = grid.columns().map(|col| col.copied().collect()).collect();
+        assert_eq!(columns, [b"adg".to_vec(), b"be".to_vec(), b"cf".to_vec()]);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = Grid::new("abc\ndef");
+        let lines = |lines: Vec<Strided<'_, u8>>| -> Vec<String> {
+            lines
+                .into_iter()
+                .map(|line| line.map(|&b| b as char).collect())
+                .collect()
+        };
+        assert_eq!(lines(grid.diagonals().collect()), ["ae", "bf", "c", "d"]);
+        assert_eq!(
+            lines(grid.anti_diagonals().collect()),
+            ["a", "bd", "ce", "f"]
+        );
+    }
+
+    #[test]
+    fn test_lines_of_ragged_and_thin_grids() {
+        let grid = Grid::new("abc\nde");
+        let diagonal: Vec<u8> = grid.diagonals().nth(1).unwrap().copied().collect();
+        assert_eq!(diagonal, b"b");
+        let anti: Vec<Vec<u8>> = grid
+            .anti_diagonals()
+            .map(|line| line.copied().collect())
+            .collect();
+        assert_eq!(anti, [&b"a"[..], b"bd", b"ce"]);
+
+        let column = Grid::new("a\nb\nc");
+        assert_eq!(column.anti_diagonals().count(), 3);
+        assert!(column.anti_diagonals().all(|line| line.count() == 1));
+        assert_eq!(Grid::new("").diagonals().count(), 0);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let grid = Grid::from_fn(3, 2, |pos| pos.row * 10 + pos.col);
+        assert_eq!(grid.get(1, 2), Some(&12));
+        assert_eq!(grid.height(), 2);
+        assert!(Grid::from_fn(0, 5, |_| 0).is_empty());
+    }
+
+    #[test]
+    fn test_equality() {
+        assert_eq!(Grid::new("ab\ncd"), Grid::parse("ab\ncd\n").unwrap());
+        assert_ne!(Grid::new("ab\ncd"), Grid::new("abcd"));
+        assert_ne!(Grid::new("ab\ncd"), Grid::new("ab\ncx"));
+        assert_eq!(Grid::filled(3, 0, b'.'), Grid::new(""));
+    }
+
+    #[test]
+    fn test_hash_matches_equality() {
+        use std::collections::HashSet;
+
+        let grids: HashSet<Grid> = [
+            Grid::new("ab\ncd"),
+            Grid::parse("ab\ncd\n").unwrap(),
+            Grid::new("abcd"),
+            Grid::new(""),
+            Grid::filled(3, 0, b'.'),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(grids.len(), 3);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = Grid::new("ab\ncd").map(|&b| b == b'c');
+        assert_eq!(grid.get(1, 0), Some(&true));
+        assert_eq!(grid.get(0, 0), Some(&false));
+    }
+
     #[test]
     fn test_get_first_element() {
         let input = "CMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
@@ -271,134 +999,89 @@ mod tests {
     }
 
     #[test]
-    fn get_point_in_direction_top() {
+    fn position_in_direction_top() {
         let input = "MCMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 1,
-                col: 1,
-                value: b'C',
-            },
-            &Directions::Top,
-        );
+        let found = grid.position_in_direction(Position::new(1, 1), Directions::Top);
 
-        let expected = Some(Point {
-            row: 0,
-            col: 1,
-            value: b'C',
-        });
-        assert_eq!(found, expected);
+        let expected = Position::new(0, 1);
+        assert_eq!(found, Some(expected));
+        assert_eq!(grid.get_at(expected), Some(&b'C'));
     }
 
     #[test]
-    fn get_point_in_direction_bottom() {
+    fn position_in_direction_bottom() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nACXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 1,
-                col: 1,
-                value: b'C',
-            },
-            &Directions::Bottom,
-        );
+        let found = grid.position_in_direction(Position::new(1, 1), Directions::Bottom);
 
-        let expected = Some(Point {
-            row: 2,
-            col: 1,
-            value: b'C',
-        });
-        assert_eq!(found, expected);
+        let expected = Position::new(2, 1);
+        assert_eq!(found, Some(expected));
+        assert_eq!(grid.get_at(expected), Some(&b'C'));
     }
 
     #[test]
-    fn get_point_in_direction_left() {
+    fn position_in_direction_left() {
         let input = "MMMSXXMASM\nCSAMXMSMSA\nAMXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 1,
-                col: 1,
-                value: b'C',
-            },
-            &Directions::Left,
-        );
+        let found = grid.position_in_direction(Position::new(1, 1), Directions::Left);
 
-        let expected = Some(Point {
-            row: 1,
-            col: 0,
-            value: b'C',
-        });
-        assert_eq!(found, expected);
+        let expected = Position::new(1, 0);
+        assert_eq!(found, Some(expected));
+        assert_eq!(grid.get_at(expected), Some(&b'C'));
     }
 
     #[test]
-    fn get_point_in_direction_right() {
+    fn position_in_direction_right() {
         let input = "MMMSXXMASM\nMSCMXMSMSA\nAMXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 1,
-                col: 1,
-                value: b'C',
-            },
-            &Directions::Right,
-        );
+        let found = grid.position_in_direction(Position::new(1, 1), Directions::Right);
 
-        let expected = Some(Point {
-            row: 1,
-            col: 2,
-            value: b'C',
-        });
-        assert_eq!(found, expected);
+        let expected = Position::new(1, 2);
+        assert_eq!(found, Some(expected));
+        assert_eq!(grid.get_at(expected), Some(&b'C'));
     }
 
     #[test]
-    fn get_point_in_direction_out_of_bounds() {
+    fn position_in_direction_out_of_bounds() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 0,
-                col: 0,
-                value: b'C',
-            },
-            &Directions::Top,
-        );
+        let found = grid.position_in_direction(Position::new(0, 0), Directions::Top);
 
         assert_eq!(found, None);
     }
 
     #[test]
-    fn get_point_in_direction_out_of_bounds_overflow() {
+    fn position_in_direction_out_of_bounds_overflow() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 2,
-                col: 8,
-                value: b'C',
-            },
-            &Directions::BottomRight,
-        );
+        let found = grid.position_in_direction(Position::new(2, 8), Directions::BottomRight);
+
+        assert_eq!(found, None);
+    }
+
+    #[test]
+    fn get_huge_row_does_not_overflow() {
+        let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
+        let grid = Grid::new(input);
+        assert_eq!(grid.get(usize::MAX, 1), None);
+    }
+
+    #[test]
+    fn position_in_direction_from_max_row() {
+        let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\n";
+        let grid = Grid::new(input);
+        let found = grid.position_in_direction(Position::new(isize::MAX, 0), Directions::Bottom);
 
         assert_eq!(found, None);
     }
 
     #[test]
-    fn get_point_in_direction_right_side() {
+    fn position_in_direction_right_side() {
         let input = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
 
         let grid = Grid::new(input);
-        let found = grid.get_point_in_direction(
-            &Point {
-                row: 7,
-                col: 9,
-                value: b'A',
-            },
-            &Directions::BottomRight,
-        );
+        let found = grid.position_in_direction(Position::new(7, 9), Directions::BottomRight);
 
         assert_eq!(found, None);
     }
@@ -0,0 +1,384 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::utils::{grid::Grid, position::Position};
+
+pub type Rgb = [u8; 3];
+
+/// A grid drawn as pixels, each cell a scale x scale square. Cells missing from
+/// a ragged last row are black. Written as binary PPM/PGM or as an uncompressed
+/// PNG, so no image library is needed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    gray: bool,
+    data: Vec<u8>,
+}
+
+impl<T> Grid<T> {
+    pub fn to_image(&self, scale: usize, color: impl FnMut(&T) -> Rgb) -> Image {
+        Image::from_cells(self, scale, false, color)
+    }
+
+    // Single-channel image, smaller files for black and white puzzles
+    pub fn to_gray_image(&self, scale: usize, mut shade: impl FnMut(&T) -> u8) -> Image {
+        Image::from_cells(self, scale, true, |value| [shade(value); 3])
+    }
+}
+
+impl Image {
+    // Gray images keep only the first channel of each pixel
+    fn from_cells<T>(
+        grid: &Grid<T>,
+        scale: usize,
+        gray: bool,
+        mut pixel: impl FnMut(&T) -> Rgb,
+    ) -> Self {
+        assert!(scale > 0, "Pixel scale must be at least 1");
+        let channels = if gray { 1 } else { 3 };
+        let width = grid.width() * scale;
+        let height = grid.height() * scale;
+
+        let mut data = Vec::with_capacity(width * height * channels);
+        for row in 0..grid.height() {
+            let mut line = Vec::with_capacity(width * channels);
+            for col in 0..grid.width() {
+                let value = match grid.get_at(Position::new(row as isize, col as isize)) {
+                    Some(value) => pixel(value),
+                    None => [0; 3],
+                };
+                for _ in 0..scale {
+                    line.extend_from_slice(&value[..channels]);
+                }
+            }
+            for _ in 0..scale {
+                data.extend_from_slice(&line);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            gray,
+            data,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn channels(&self) -> usize {
+        if self.gray { 1 } else { 3 }
+    }
+
+    // Binary PPM (P6), gray images are expanded to RGB
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        if self.gray {
+            for &shade in &self.data {
+                out.write_all(&[shade; 3])?;
+            }
+            Ok(())
+        } else {
+            out.write_all(&self.data)
+        }
+    }
+
+    // Binary PGM (P5), colour images are converted to luma
+    pub fn write_pgm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width, self.height)?;
+        if self.gray {
+            out.write_all(&self.data)
+        } else {
+            let luma: Vec<u8> = self
+                .data
+                .chunks_exact(3)
+                .map(|rgb| {
+                    let [r, g, b] = [rgb[0], rgb[1], rgb[2]].map(u32::from);
+                    ((r * 299 + g * 587 + b * 114) / 1000) as u8
+                })
+                .collect();
+            out.write_all(&luma)
+        }
+    }
+
+    /// 8-bit grayscale or RGB PNG. The pixel data goes in stored (uncompressed)
+    /// deflate blocks, so files are as big as a PPM but open anywhere. PNG has
+    /// no empty images, so a 0 width or height is an InvalidInput error, as is
+    /// one past the 2^31 - 1 the format allows.
+    pub fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+        let dimension = |size: usize| {
+            u32::try_from(size)
+                .ok()
+                .filter(|&size| size > 0 && size <= i32::MAX as u32)
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("PNG can't be {}x{}", self.width, self.height),
+                    )
+                })
+        };
+        let (width, height) = (dimension(self.width)?, dimension(self.height)?);
+
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        // Bit depth, colour type, then default compression, filter and interlace
+        header.extend_from_slice(&[8, if self.gray { 0 } else { 2 }, 0, 0, 0]);
+        write_chunk(out, b"IHDR", &header)?;
+
+        // Every scanline starts with filter type 0 (none)
+        let stride = self.width * self.channels();
+        let mut raw = Vec::with_capacity((stride + 1) * self.height);
+        for row in 0..self.height {
+            raw.push(0);
+            raw.extend_from_slice(&self.data[row * stride..(row + 1) * stride]);
+        }
+        write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(out, b"IEND", &[])
+    }
+
+    // Picks the format from the extension: png, ppm or pgm
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let write = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => Self::write_png,
+            Some("ppm") => Self::write_ppm,
+            Some("pgm") => Self::write_pgm,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Unknown image format: {}", path.display()),
+                ));
+            }
+        };
+        // Encoded up front so an image that can't be written leaves no file behind
+        let mut bytes = Vec::new();
+        write(self, &mut bytes)?;
+        fs::write(path, bytes)
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&crc.to_be_bytes())
+}
+
+// A zlib stream of stored deflate blocks, each holding at most 65535 bytes
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        stream.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        stream.push(last as u8);
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let crc = bytes.into_iter().fold(0xFFFF_FFFF, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    });
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+/// Numbered image files for the frames of a simulation, e.g. frames/step_00042.png,
+/// to be looked through or stitched into an animation offline
+pub struct FrameSequence {
+    dir: PathBuf,
+    prefix: String,
+    extension: String,
+    next: usize,
+}
+
+impl FrameSequence {
+    // Creates dir if needed, extension picks the format as in Image::save
+    pub fn new(dir: impl Into<PathBuf>, prefix: &str, extension: &str) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            prefix: prefix.to_string(),
+            extension: extension.to_string(),
+            next: 0,
+        })
+    }
+
+    // Number of frames written so far
+    pub fn len(&self) -> usize {
+        self.next
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next == 0
+    }
+
+    // Saves image as the next frame and returns its path
+    pub fn write(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let name = format!("{}{:05}.{}", self.prefix, self.next, self.extension);
+        let path = self.dir.join(name);
+        image.save(&path)?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        Grid::new("#.\n.#").to_gray_image(2, |&b| if b == b'#' { 0 } else { 255 })
+    }
+
+    #[test]
+    fn test_scale_and_ragged_rows() {
+        let image = Grid::new("ab\nc").to_image(2, |&b| [b, 0, 0]);
+        assert_eq!((image.width(), image.height()), (4, 4));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels: Vec<u8> = ppm[header.len()..].chunks(3).map(|rgb| rgb[0]).collect();
+        assert_eq!(pixels[..8], *b"aabbaabb");
+        assert_eq!(pixels[8..], *b"cc\0\0cc\0\0");
+    }
+
+    #[test]
+    fn test_pgm() {
+        let mut pgm = Vec::new();
+        checkerboard().write_pgm(&mut pgm).unwrap();
+        let mut expected = b"P5\n4 4\n255\n".to_vec();
+        for row in [[0, 0, 255, 255], [255, 255, 0, 0]] {
+            expected.extend_from_slice(&row);
+            expected.extend_from_slice(&row);
+        }
+        assert_eq!(pgm, expected);
+
+        let mut luma = Vec::new();
+        let white = Grid::new("x").to_image(1, |_| [255, 255, 255]);
+        white.write_pgm(&mut luma).unwrap();
+        assert_eq!(luma.last(), Some(&255));
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_png_layout() {
+        let mut png = Vec::new();
+        checkerboard().write_png(&mut png).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 4, 0, 0, 0, 4]);
+        assert_eq!(png[25], 0);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xAE\x42\x60\x82");
+
+        // 4 scanlines of a filter byte and 4 pixels, in one stored block
+        let idat = &png[33..];
+        assert_eq!(&idat[..4], &(2 + 5 + 20 + 4u32).to_be_bytes());
+        assert_eq!(&idat[4..8], b"IDAT");
+        assert_eq!(&idat[8..15], &[0x78, 0x01, 1, 20, 0, 0xEB, 0xFF]);
+    }
+
+    #[test]
+    fn test_png_rejects_empty_and_oversized() {
+        let empty = Grid::new("").to_image(1, |_| [0, 0, 0]);
+        let err = empty.write_png(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        let no_rows = Grid::filled(3, 0, b'.').to_gray_image(2, |_| 0);
+        let mut png = Vec::new();
+        assert!(no_rows.write_png(&mut png).is_err());
+        assert!(png.is_empty());
+
+        let huge = Image {
+            width: 1 << 31,
+            height: 1,
+            gray: true,
+            data: Vec::new(),
+        };
+        let err = huge.write_png(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_stored_blocks_split() {
+        let data = vec![7; 70_000];
+        let stream = zlib_stored(&data);
+        assert_eq!(stream.len(), 2 + 2 * 5 + data.len() + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + 0xFFFF], 1);
+    }
+
+    #[test]
+    fn test_frame_sequence() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = FrameSequence::new(&dir, "step_", "pgm").unwrap();
+        let image = checkerboard();
+        frames.write(&image).unwrap();
+        let second = frames.write(&image).unwrap();
+        assert_eq!(second, dir.join("step_00001.pgm"));
+        assert_eq!(frames.len(), 2);
+        assert_eq!(fs::read(&second).unwrap().len(), 11 + 16);
+
+        let unknown = image.save(dir.join("frame.bmp")).unwrap_err();
+        assert_eq!(unknown.kind(), io::ErrorKind::InvalidInput);
+        let empty = Grid::new("").to_gray_image(1, |_| 0);
+        assert!(empty.save(dir.join("empty.png")).is_err());
+        assert!(!dir.join("empty.png").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,308 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::marker::PhantomData;
+
+use crate::utils::parse::ParseError;
+
+/// Integer types that can be parsed straight from ASCII digits
+pub trait Integer: Copy + Default {
+    const SIGNED: bool;
+
+    // Accumulates in the target type, negatives are built downwards so MIN parses
+    fn from_digits(digits: &[u8], negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($type_:ident, $signed_:expr) => {
+        impl Integer for $type_ {
+            const SIGNED: bool = $signed_;
+
+            fn from_digits(digits: &[u8], negative: bool) -> Option<Self> {
+                let mut value: $type_ = 0;
+                for &digit in digits {
+                    let digit = (digit - b'0') as $type_;
+                    value = value.checked_mul(10)?;
+                    value = if negative {
+                        value.checked_sub(digit)?
+                    } else {
+                        value.checked_add(digit)?
+                    };
+                }
+                Some(value)
+            }
+        }
+    };
+}
+
+impl_integer!(i8, true);
+impl_integer!(i16, true);
+impl_integer!(i32, true);
+impl_integer!(i64, true);
+impl_integer!(i128, true);
+impl_integer!(isize, true);
+impl_integer!(u8, false);
+impl_integer!(u16, false);
+impl_integer!(u32, false);
+impl_integer!(u64, false);
+impl_integer!(u128, false);
+impl_integer!(usize, false);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IntError {
+    // The number starting at this byte offset doesn't fit the target type
+    Overflow { offset: usize },
+    // In strict mode, the token starting at this byte offset isn't a number
+    NotANumber { offset: usize },
+    WrongCount { expected: usize, found: usize },
+}
+
+impl IntError {
+    pub fn at_line(&self, line: usize) -> ParseError {
+        ParseError::new(line, self.to_string())
+    }
+}
+
+impl Display for IntError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IntError::Overflow { offset } => {
+                write!(f, "number at column {} is out of range", offset + 1)
+            }
+            IntError::NotANumber { offset } => {
+                write!(f, "expected a number at column {}", offset + 1)
+            }
+            IntError::WrongCount { expected, found } => {
+                write!(f, "expected {expected} numbers, found {found}")
+            }
+        }
+    }
+}
+
+impl Error for IntError {}
+
+/// Iterator over the integers in a byte slice, anything that isn't a digit is a separator.
+/// For signed types a `-` directly before a digit is a sign unless it follows a digit,
+/// so `10-20` reads as a range rather than `10, -20`.
+///
+/// In strict mode only whitespace separates, and every token must be a whole
+/// number (with a leading `-` for signed types) or it's a `NotANumber` error.
+pub struct Integers<'a, T> {
+    bytes: &'a [u8],
+    pos: usize,
+    strict: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T: Integer> Integers<'_, T> {
+    fn next_token(&mut self) -> Option<Result<T, IntError>> {
+        let bytes = self.bytes;
+        let mut pos = self.pos;
+
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if pos == bytes.len() {
+            self.pos = pos;
+            return None;
+        }
+
+        let start = pos;
+        while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        self.pos = pos;
+
+        let token = &bytes[start..pos];
+        let (negative, digits) = match token.split_first() {
+            Some((b'-', rest)) if T::SIGNED => (true, rest),
+            _ => (false, token),
+        };
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            return Some(Err(IntError::NotANumber { offset: start }));
+        }
+
+        Some(T::from_digits(digits, negative).ok_or(IntError::Overflow { offset: start }))
+    }
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = Result<T, IntError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.strict {
+            return self.next_token();
+        }
+
+        let bytes = self.bytes;
+        let mut pos = self.pos;
+
+        while pos < bytes.len() && !bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        if pos == bytes.len() {
+            self.pos = pos;
+            return None;
+        }
+
+        let start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        self.pos = pos;
+
+        let negative = T::SIGNED
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit());
+        let offset = if negative { start - 1 } else { start };
+
+        Some(T::from_digits(&bytes[start..pos], negative).ok_or(IntError::Overflow { offset }))
+    }
+}
+
+pub fn integers<T: Integer>(input: &(impl AsRef<[u8]> + ?Sized)) -> Integers<'_, T> {
+    Integers {
+        bytes: input.as_ref(),
+        pos: 0,
+        strict: false,
+        marker: PhantomData,
+    }
+}
+
+// Whitespace-separated numbers where anything else is an error, for inputs that are only numbers
+pub fn strict_integers<T: Integer>(input: &(impl AsRef<[u8]> + ?Sized)) -> Integers<'_, T> {
+    Integers {
+        strict: true,
+        ..integers(input)
+    }
+}
+
+/// Reads exactly N integers, e.g. `exact_integers::<i32, 2>("mul(2,4)")`
+pub fn exact_integers<T: Integer, const N: usize>(
+    input: &(impl AsRef<[u8]> + ?Sized),
+) -> Result<[T; N], IntError> {
+    exact(integers(input))
+}
+
+// Reads exactly N whitespace-separated numbers, rejecting any other token
+pub fn exact_strict_integers<T: Integer, const N: usize>(
+    input: &(impl AsRef<[u8]> + ?Sized),
+) -> Result<[T; N], IntError> {
+    exact(strict_integers(input))
+}
+
+fn exact<T: Integer, const N: usize>(numbers: Integers<'_, T>) -> Result<[T; N], IntError> {
+    let mut values = [T::default(); N];
+    let mut found = 0;
+
+    for value in numbers {
+        if found < N {
+            values[found] = value?;
+        }
+        found += 1;
+    }
+
+    if found != N {
+        return Err(IntError::WrongCount { expected: N, found });
+    }
+
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<T: Integer>(input: &str) -> Vec<T> {
+        integers::<T>(input).map(|value| value.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(collect::<u32>("10000   20000"), [10000, 20000]);
+        assert_eq!(collect::<u32>("x-3,4y"), [3, 4]);
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(collect::<i32>("p=-3,4 v=12,-7"), [-3, 4, 12, -7]);
+    }
+
+    #[test]
+    fn test_range_is_not_negative() {
+        assert_eq!(collect::<i64>("10-20"), [10, 20]);
+    }
+
+    #[test]
+    fn test_bytes_input() {
+        let values: Vec<_> = integers::<u8>(b"1 2 3".as_slice()).collect();
+        assert_eq!(values, [Ok(1), Ok(2), Ok(3)]);
+    }
+
+    #[test]
+    fn test_no_numbers() {
+        assert!(collect::<i32>("").is_empty());
+        assert!(collect::<i32>("mul(,)").is_empty());
+    }
+
+    #[test]
+    fn test_min_and_max() {
+        assert_eq!(collect::<i8>("-128 127"), [-128, 127]);
+        assert_eq!(collect::<u8>("255 0"), [255, 0]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let values: Vec<_> = integers::<u8>("1 256").collect();
+        assert_eq!(values, [Ok(1), Err(IntError::Overflow { offset: 2 })]);
+    }
+
+    #[test]
+    fn test_strict_rejects_other_tokens() {
+        let values: Vec<_> = strict_integers::<i32>(" 1 -2\t3 x 4- 5").collect();
+        assert_eq!(
+            values,
+            [
+                Ok(1),
+                Ok(-2),
+                Ok(3),
+                Err(IntError::NotANumber { offset: 8 }),
+                Err(IntError::NotANumber { offset: 10 }),
+                Ok(5)
+            ]
+        );
+        assert_eq!(
+            strict_integers::<u8>("-3 256").collect::<Vec<_>>(),
+            [
+                Err(IntError::NotANumber { offset: 0 }),
+                Err(IntError::Overflow { offset: 3 })
+            ]
+        );
+        assert_eq!(
+            exact_strict_integers::<i32, 2>("1000é   30000"),
+            Err(IntError::NotANumber { offset: 0 })
+        );
+        assert_eq!(exact_strict_integers::<i32, 2>("10 -20"), Ok([10, -20]));
+    }
+
+    #[test]
+    fn test_exact() {
+        assert_eq!(exact_integers::<i32, 2>("mul(2,44)"), Ok([2, 44]));
+    }
+
+    #[test]
+    fn test_exact_wrong_count() {
+        assert_eq!(
+            exact_integers::<i32, 2>("1 2 3"),
+            Err(IntError::WrongCount {
+                expected: 2,
+                found: 3
+            })
+        );
+        let err = exact_integers::<i32, 2>("1").unwrap_err();
+        assert_eq!(
+            format!("{}", err.at_line(4)),
+            "line 4: expected 2 numbers, found 1"
+        );
+    }
+}
@@ -1,2 +1,23 @@
+pub mod aho_corasick;
+pub mod cells;
+pub mod cycle;
+pub mod direction;
 pub mod grid;
+pub mod image;
+pub mod ints;
+pub mod ordering;
+pub mod parse;
+pub mod pathfinding;
+pub mod position;
+pub mod ray;
+pub mod region;
+pub mod render;
+pub mod rng;
+pub mod sections;
+pub mod simulation;
 pub mod solution;
+pub mod sparse;
+pub mod torus;
+pub mod transform;
+pub mod visualize;
+pub mod word_search;
@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::Hash;
+
+/// Why a set of `X|Y` rules can't order some items
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OrderingError<T> {
+    /// Both X|Y and Y|X were given, or X|X
+    Contradiction(T, T),
+    /// The rules between the items form a loop, listed in order with the first
+    /// item repeated at the end
+    Cycle(Vec<T>),
+}
+
+impl<T: Display + PartialEq> Display for OrderingError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingError::Contradiction(a, b) if a == b => {
+                write!(f, "rule {a}|{b} orders a page before itself")
+            }
+            OrderingError::Contradiction(a, b) => {
+                write!(f, "contradictory rules {a}|{b} and {b}|{a}")
+            }
+            OrderingError::Cycle(items) => {
+                let path: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "cyclic rules {}", path.join(" -> "))
+            }
+        }
+    }
+}
+
+impl<T: Debug + Display + PartialEq> Error for OrderingError<T> {}
+
+/// A set of `X|Y` constraints meaning X must come before Y whenever both appear.
+/// The rules don't need to be a total order overall, only among the items being ordered.
+#[derive(Clone, Debug, Default)]
+pub struct OrderingRules<T> {
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T: Copy + Eq + Hash> OrderingRules<T> {
+    pub fn new() -> Self {
+        Self {
+            successors: HashMap::new(),
+        }
+    }
+
+    pub fn from_rules(rules: impl IntoIterator<Item = (T, T)>) -> Result<Self, OrderingError<T>> {
+        let mut ordering = Self::new();
+        for (before, after) in rules {
+            ordering.add_rule(before, after)?;
+        }
+        Ok(ordering)
+    }
+
+    pub fn add_rule(&mut self, before: T, after: T) -> Result<(), OrderingError<T>> {
+        if before == after || self.must_precede(after, before) {
+            return Err(OrderingError::Contradiction(before, after));
+        }
+
+        self.successors.entry(before).or_default().insert(after);
+        Ok(())
+    }
+
+    pub fn must_precede(&self, before: T, after: T) -> bool {
+        self.successors
+            .get(&before)
+            .is_some_and(|successors| successors.contains(&after))
+    }
+
+    // Only direct rules are considered, unrelated items compare equal
+    pub fn compare(&self, a: T, b: T) -> Ordering {
+        if self.must_precede(a, b) {
+            Ordering::Less
+        } else if self.must_precede(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    pub fn is_ordered(&self, items: &[T]) -> bool {
+        items.iter().enumerate().all(|(i, &later)| {
+            items[..i]
+                .iter()
+                .all(|&earlier| !self.must_precede(later, earlier))
+        })
+    }
+
+    /// Topologically sorts the items using only the rules between them.
+    /// Ties keep their original relative order, so sorting an ordered slice is a no-op.
+    pub fn sort(&self, items: &[T]) -> Result<Vec<T>, OrderingError<T>> {
+        let mut in_degree: Vec<usize> = items
+            .iter()
+            .map(|&item| {
+                items
+                    .iter()
+                    .filter(|&&other| self.must_precede(other, item))
+                    .count()
+            })
+            .collect();
+
+        let mut sorted = Vec::with_capacity(items.len());
+        let mut placed = vec![false; items.len()];
+
+        while sorted.len() < items.len() {
+            let Some(next) = (0..items.len()).find(|&i| !placed[i] && in_degree[i] == 0) else {
+                return Err(OrderingError::Cycle(self.find_cycle(items, &placed)));
+            };
+
+            placed[next] = true;
+            sorted.push(items[next]);
+            for (i, &item) in items.iter().enumerate() {
+                if !placed[i] && self.must_precede(items[next], item) {
+                    in_degree[i] -= 1;
+                }
+            }
+        }
+
+        Ok(sorted)
+    }
+
+    // Every unplaced item has an unplaced predecessor, so walking backwards must revisit one
+    fn find_cycle(&self, items: &[T], placed: &[bool]) -> Vec<T> {
+        let remaining: Vec<T> = items
+            .iter()
+            .zip(placed)
+            .filter(|&(_, &placed)| !placed)
+            .map(|(&item, _)| item)
+            .collect();
+
+        let mut path = vec![remaining[0]];
+        loop {
+            let current = path[path.len() - 1];
+            let predecessor = *remaining
+                .iter()
+                .find(|&&other| self.must_precede(other, current))
+                .expect("Unplaced item without a predecessor");
+
+            if let Some(start) = path.iter().position(|&item| item == predecessor) {
+                let mut cycle = path[start..].to_vec();
+                cycle.push(predecessor);
+                // Walked against the rules, flip so each item must precede the next
+                cycle.reverse();
+                return cycle;
+            }
+            path.push(predecessor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_rules() -> OrderingRules<u32> {
+        OrderingRules::from_rules([(47, 53), (97, 13), (97, 61), (75, 29), (61, 13), (29, 13)])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compare() {
+        let rules = example_rules();
+        assert_eq!(rules.compare(47, 53), Ordering::Less);
+        assert_eq!(rules.compare(53, 47), Ordering::Greater);
+        assert_eq!(rules.compare(47, 13), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_is_ordered() {
+        let rules = example_rules();
+        assert!(rules.is_ordered(&[97, 61, 13]));
+        assert!(!rules.is_ordered(&[13, 61, 97]));
+    }
+
+    #[test]
+    fn test_sort() {
+        let rules = example_rules();
+        assert_eq!(rules.sort(&[13, 29, 75]).unwrap(), vec![75, 29, 13]);
+        assert_eq!(rules.sort(&[97, 61, 13]).unwrap(), vec![97, 61, 13]);
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let err = OrderingRules::from_rules([(1, 2), (2, 1)]).unwrap_err();
+        assert_eq!(err, OrderingError::Contradiction(2, 1));
+        assert_eq!(format!("{err}"), "contradictory rules 2|1 and 1|2");
+    }
+
+    #[test]
+    fn test_self_contradiction() {
+        let err = OrderingRules::from_rules([(4, 4)]).unwrap_err();
+        assert_eq!(err, OrderingError::Contradiction(4, 4));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules = OrderingRules::from_rules([(1, 2), (2, 3), (3, 1), (0, 1)]).unwrap();
+        let err = rules.sort(&[0, 1, 2, 3]).unwrap_err();
+        assert_eq!(err, OrderingError::Cycle(vec![1, 2, 3, 1]));
+        assert_eq!(format!("{err}"), "cyclic rules 1 -> 2 -> 3 -> 1");
+    }
+
+    #[test]
+    fn test_cycle_outside_items_is_ignored() {
+        let rules = OrderingRules::from_rules([(1, 2), (2, 3), (3, 1)]).unwrap();
+        assert_eq!(rules.sort(&[3, 2]).unwrap(), vec![2, 3]);
+    }
+}
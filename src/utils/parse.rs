@@ -0,0 +1,56 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// Error returned by the day parsers when the puzzle input is malformed.
+/// `line` and `section` are 1-based so they match what an editor shows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub section: Option<usize>,
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            section: None,
+            line,
+            message: message.into(),
+        }
+    }
+
+    pub fn in_section(self, section: usize) -> Self {
+        Self {
+            section: Some(section),
+            ..self
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(section) = self.section {
+            write!(f, "section {section}, ")?;
+        }
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_includes_line() {
+        let err = ParseError::new(3, "expected two numbers");
+        assert_eq!(format!("{err}"), "line 3: expected two numbers");
+    }
+
+    #[test]
+    fn test_display_includes_section() {
+        let err = ParseError::new(3, "expected two numbers").in_section(2);
+        assert_eq!(format!("{err}"), "section 2, line 3: expected two numbers");
+    }
+}
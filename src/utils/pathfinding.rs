@@ -0,0 +1,415 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use crate::utils::{cells::Cells, position::Position};
+
+/// A single route, from a source to a target inclusive
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path {
+    pub cost: u64,
+    pub cells: Vec<Position>,
+}
+
+/// Result of a search: the distance to every reached cell from the nearest
+/// source, and for each cell every neighbour it can be reached from on some
+/// shortest path, so single paths, all paths and the cells they cover can be
+/// rebuilt afterwards
+#[derive(Clone, Debug, Default)]
+pub struct ShortestPaths {
+    distances: HashMap<Position, u64>,
+    parents: HashMap<Position, Vec<Position>>,
+}
+
+impl ShortestPaths {
+    pub fn distance(&self, pos: Position) -> Option<u64> {
+        self.distances.get(&pos).copied()
+    }
+
+    // The full distance map, unreached cells are missing
+    pub fn distances(&self) -> &HashMap<Position, u64> {
+        &self.distances
+    }
+
+    // The closest reached target and its distance, ties go to the first given
+    pub fn nearest(&self, targets: impl IntoIterator<Item = Position>) -> Option<(Position, u64)> {
+        let mut best: Option<(Position, u64)> = None;
+        for target in targets {
+            if let Some(distance) = self.distance(target)
+                && best.is_none_or(|(_, best)| distance < best)
+            {
+                best = Some((target, distance));
+            }
+        }
+        best
+    }
+
+    // One shortest path to target, following the first recorded parent back to a source
+    pub fn path_to(&self, target: Position) -> Option<Path> {
+        let cost = self.distance(target)?;
+        let mut cells = vec![target];
+        let mut current = target;
+
+        while let Some(&parent) = self.parents.get(&current).and_then(|p| p.first()) {
+            cells.push(parent);
+            current = parent;
+        }
+
+        cells.reverse();
+        Some(Path { cost, cells })
+    }
+
+    /// Every distinct shortest path to target. There can be exponentially many,
+    /// prefer cells_on_shortest_paths when only the covered cells matter.
+    pub fn all_paths_to(&self, target: Position) -> Vec<Path> {
+        let Some(cost) = self.distance(target) else {
+            return Vec::new();
+        };
+
+        let mut paths = Vec::new();
+        let mut stack = vec![vec![target]];
+        while let Some(cells) = stack.pop() {
+            let last = *cells.last().expect("paths are never empty");
+            match self.parents.get(&last) {
+                Some(parents) if !parents.is_empty() => {
+                    for &parent in parents.iter().rev() {
+                        let mut longer = cells.clone();
+                        longer.push(parent);
+                        stack.push(longer);
+                    }
+                }
+                _ => {
+                    let mut cells = cells;
+                    cells.reverse();
+                    paths.push(Path { cost, cells });
+                }
+            }
+        }
+
+        paths
+    }
+
+    // Cells on any shortest path to whichever targets are nearest
+    pub fn cells_on_shortest_paths(
+        &self,
+        targets: impl IntoIterator<Item = Position>,
+    ) -> HashSet<Position> {
+        let reached: Vec<(Position, u64)> = targets
+            .into_iter()
+            .filter_map(|target| Some((target, self.distance(target)?)))
+            .collect();
+        let Some(best) = reached.iter().map(|&(_, distance)| distance).min() else {
+            return HashSet::new();
+        };
+
+        let mut cells = HashSet::new();
+        let mut stack: Vec<Position> = reached
+            .into_iter()
+            .filter(|&(_, distance)| distance == best)
+            .map(|(target, _)| target)
+            .collect();
+
+        while let Some(pos) = stack.pop() {
+            if cells.insert(pos) {
+                stack.extend(self.parents.get(&pos).into_iter().flatten());
+            }
+        }
+
+        cells
+    }
+
+    // A source starts at zero, even if it is also reachable from another one
+    fn add_source(&mut self, source: Position) -> bool {
+        if self.distances.contains_key(&source) {
+            return false;
+        }
+        self.distances.insert(source, 0);
+        true
+    }
+}
+
+/// Unweighted search from every source at once. edges pushes the cells
+/// reachable in one step from a cell.
+pub fn bfs(
+    sources: impl IntoIterator<Item = Position>,
+    mut edges: impl FnMut(Position, &mut Vec<Position>),
+) -> ShortestPaths {
+    let mut result = ShortestPaths::default();
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if result.add_source(source) {
+            queue.push_back(source);
+        }
+    }
+
+    let mut next = Vec::new();
+    while let Some(pos) = queue.pop_front() {
+        let distance = result.distances[&pos] + 1;
+
+        next.clear();
+        edges(pos, &mut next);
+        for &to in &next {
+            match result.distances.get(&to) {
+                None => {
+                    result.distances.insert(to, distance);
+                    result.parents.insert(to, vec![pos]);
+                    queue.push_back(to);
+                }
+                Some(&known) if known == distance => {
+                    result.parents.entry(to).or_default().push(pos);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    result
+}
+
+/// Weighted search from every source at once. edges pushes (cell, cost) pairs
+/// for each step out of a cell. With targets it stops once the nearest target
+/// is settled, and heuristic turns it into A*: it must never overestimate the
+/// remaining cost to the nearest target, `|_| 0` gives plain Dijkstra.
+pub fn dijkstra(
+    sources: impl IntoIterator<Item = Position>,
+    targets: &HashSet<Position>,
+    mut edges: impl FnMut(Position, &mut Vec<(Position, u64)>),
+    mut heuristic: impl FnMut(Position) -> u64,
+) -> (ShortestPaths, Option<Position>) {
+    let mut result = ShortestPaths::default();
+    let mut settled = HashSet::new();
+    let mut heap = BinaryHeap::new();
+    let mut seeds = HashSet::new();
+    for source in sources {
+        if result.add_source(source) {
+            seeds.insert(source);
+            heap.push(Reverse((heuristic(source), 0, source)));
+        }
+    }
+
+    let mut next = Vec::new();
+    while let Some(Reverse((_, distance, pos))) = heap.pop() {
+        if !settled.insert(pos) {
+            continue;
+        }
+        if targets.contains(&pos) {
+            return (result, Some(pos));
+        }
+
+        next.clear();
+        edges(pos, &mut next);
+        for &(to, cost) in &next {
+            // Parents only point at settled cells, so zero cost edges can't form loops.
+            // Sources are path starts, even when another source reaches them for free.
+            if settled.contains(&to) || seeds.contains(&to) {
+                continue;
+            }
+            let candidate = distance + cost;
+            match result.distances.get(&to) {
+                Some(&known) if known < candidate => {}
+                Some(&known) if known == candidate => {
+                    result.parents.entry(to).or_default().push(pos);
+                }
+                _ => {
+                    result.distances.insert(to, candidate);
+                    result.parents.insert(to, vec![pos]);
+                    heap.push(Reverse((candidate + heuristic(to), candidate, to)));
+                }
+            }
+        }
+    }
+
+    (result, None)
+}
+
+/// Searches over a Grid or SparseGrid, moving orthogonally between existing
+/// cells. Closures get the values of the cell left and the cell entered.
+pub trait Pathfinding: Cells {
+    // Distances from the sources to every reachable cell, each step costing 1
+    fn bfs(
+        &self,
+        sources: impl IntoIterator<Item = Position>,
+        mut passable: impl FnMut(&Self::Value, &Self::Value) -> bool,
+    ) -> ShortestPaths {
+        bfs(existing(self, sources), |pos, next| {
+            let Some(from) = self.cell(pos) else {
+                return;
+            };
+            for (to_pos, to) in self.neighbors4(pos) {
+                if passable(from, to) {
+                    next.push(to_pos);
+                }
+            }
+        })
+    }
+
+    // Distances from the sources to every reachable cell, None costs are walls
+    fn dijkstra(
+        &self,
+        sources: impl IntoIterator<Item = Position>,
+        cost: impl FnMut(&Self::Value, &Self::Value) -> Option<u64>,
+    ) -> ShortestPaths {
+        let (result, _) = weighted_search(self, sources, &HashSet::new(), cost, |_| 0);
+        result
+    }
+
+    /// Cheapest path from any source to the nearest target. heuristic estimates
+    /// the cost left from a cell and must never overestimate it, e.g. the
+    /// manhattan distance to the target when every step costs at least 1.
+    fn astar(
+        &self,
+        sources: impl IntoIterator<Item = Position>,
+        targets: impl IntoIterator<Item = Position>,
+        cost: impl FnMut(&Self::Value, &Self::Value) -> Option<u64>,
+        heuristic: impl FnMut(Position) -> u64,
+    ) -> Option<Path> {
+        let targets: HashSet<Position> = targets.into_iter().collect();
+        let (result, reached) = weighted_search(self, sources, &targets, cost, heuristic);
+        result.path_to(reached?)
+    }
+}
+
+impl<C: Cells + ?Sized> Pathfinding for C {}
+
+fn existing<C: Cells + ?Sized>(
+    cells: &C,
+    sources: impl IntoIterator<Item = Position>,
+) -> Vec<Position> {
+    sources
+        .into_iter()
+        .filter(|&pos| cells.has_cell(pos))
+        .collect()
+}
+
+fn weighted_search<C: Cells + ?Sized>(
+    cells: &C,
+    sources: impl IntoIterator<Item = Position>,
+    targets: &HashSet<Position>,
+    mut cost: impl FnMut(&C::Value, &C::Value) -> Option<u64>,
+    heuristic: impl FnMut(Position) -> u64,
+) -> (ShortestPaths, Option<Position>) {
+    let edges = |pos, next: &mut Vec<(Position, u64)>| {
+        let Some(from) = cells.cell(pos) else {
+            return;
+        };
+        for (to_pos, to) in cells.neighbors4(pos) {
+            if let Some(step) = cost(from, to) {
+                next.push((to_pos, step));
+            }
+        }
+    };
+    dijkstra(existing(cells, sources), targets, edges, heuristic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::grid::Grid;
+
+    const MAZE: &str = "S..#\n.#..\n...E";
+
+    fn open(_: &u8, to: &u8) -> bool {
+        *to != b'#'
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let grid = Grid::new(MAZE);
+        let paths = grid.bfs([Position::ORIGIN], open);
+        assert_eq!(paths.distance(Position::new(2, 3)), Some(5));
+        assert_eq!(paths.distance(Position::new(0, 3)), None);
+        assert_eq!(paths.distances().len(), 10);
+
+        let path = paths.path_to(Position::new(2, 3)).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.cells.len(), 6);
+        assert_eq!(path.cells.first(), Some(&Position::ORIGIN));
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let grid = Grid::new(MAZE);
+        let paths = grid.bfs([Position::ORIGIN], open);
+        let end = Position::new(2, 3);
+
+        // Down the left side, or along the top and either way round (1, 3)
+        assert_eq!(paths.all_paths_to(end).len(), 3);
+        let cells = paths.cells_on_shortest_paths([end]);
+        assert_eq!(cells.len(), 10);
+
+        let cells = paths.cells_on_shortest_paths([Position::new(1, 2)]);
+        assert_eq!(cells.len(), 4);
+        assert!(!cells.contains(&Position::new(1, 0)));
+    }
+
+    #[test]
+    fn test_multiple_sources_and_targets() {
+        let grid = Grid::new("....\n....");
+        let paths = grid.bfs([Position::new(0, 0), Position::new(0, 3)], open);
+        assert_eq!(paths.distance(Position::new(1, 2)), Some(2));
+        assert_eq!(
+            paths.nearest([Position::new(1, 1), Position::new(0, 2)]),
+            Some((Position::new(0, 2), 1))
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_weights() {
+        // Digits are the cost of entering a cell
+        let grid = Grid::new("191\n111");
+        let cost = |_: &u8, to: &u8| Some((to - b'0') as u64);
+        let paths = grid.dijkstra([Position::ORIGIN], cost);
+        assert_eq!(paths.distance(Position::new(0, 2)), Some(4));
+        assert_eq!(paths.distance(Position::new(0, 1)), Some(9));
+    }
+
+    #[test]
+    fn test_dijkstra_walls() {
+        let grid = Grid::new(".#.");
+        let cost = |_: &u8, to: &u8| (*to != b'#').then_some(1);
+        let paths = grid.dijkstra([Position::ORIGIN], cost);
+        assert_eq!(paths.distance(Position::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = Grid::new(MAZE);
+        let end = Position::new(2, 3);
+        let cost = |_: &u8, to: &u8| (*to != b'#').then_some(1);
+        let path = grid
+            .astar([Position::ORIGIN], [end], cost, |pos| {
+                pos.manhattan(end) as u64
+            })
+            .unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.cells.last(), Some(&end));
+
+        let blocked = grid.astar([Position::ORIGIN], [Position::new(0, 3)], cost, |_| 0);
+        assert_eq!(blocked, None);
+    }
+
+    #[test]
+    fn test_zero_cost_edges() {
+        let grid = Grid::new("...");
+        let paths = grid.dijkstra([Position::ORIGIN], |_, _| Some(0));
+        let end = Position::new(0, 2);
+        assert_eq!(paths.distance(end), Some(0));
+        assert_eq!(paths.all_paths_to(end).len(), 1);
+    }
+
+    #[test]
+    fn test_zero_cost_multiple_sources() {
+        let grid = Grid::new("...");
+        let second = Position::new(0, 1);
+        let paths = grid.dijkstra([Position::ORIGIN, second], |_, _| Some(0));
+        assert_eq!(paths.path_to(second).unwrap().cells, [second]);
+        assert_eq!(paths.all_paths_to(second).len(), 1);
+        assert_eq!(paths.distance(Position::new(0, 2)), Some(0));
+    }
+
+    #[test]
+    fn test_sources_off_grid_are_ignored() {
+        let grid = Grid::new("..");
+        let paths = grid.bfs([Position::new(-1, 0)], open);
+        assert!(paths.distances().is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed (row, col) coordinate. Positions can point outside a grid, the grid
+/// decides what's in bounds. Ordering is row-major, matching grid iteration.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: isize,
+    pub col: isize,
+}
+
+/// The difference between two positions, e.g. one step in a direction or a velocity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Delta {
+    pub row: isize,
+    pub col: isize,
+}
+
+/// Smallest rectangle holding a set of positions, both corners inclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl Position {
+    pub const ORIGIN: Position = Position::new(0, 0);
+
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    pub fn manhattan(self, other: Position) -> usize {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Position) -> usize {
+        (other - self).chebyshev()
+    }
+}
+
+impl Delta {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Self { row, col }
+    }
+
+    // Length counting orthogonal steps only
+    pub fn manhattan(self) -> usize {
+        self.row.unsigned_abs() + self.col.unsigned_abs()
+    }
+
+    // Length when diagonal steps are allowed
+    pub fn chebyshev(self) -> usize {
+        self.row.unsigned_abs().max(self.col.unsigned_abs())
+    }
+}
+
+impl Bounds {
+    // The bounds of a single position
+    pub const fn new(pos: Position) -> Self {
+        Self { min: pos, max: pos }
+    }
+
+    pub fn from_positions(positions: impl IntoIterator<Item = Position>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let mut bounds = Bounds::new(positions.next()?);
+        for pos in positions {
+            bounds.include(pos);
+        }
+        Some(bounds)
+    }
+
+    // Grows the bounds to cover pos
+    pub fn include(&mut self, pos: Position) {
+        self.min = Position::new(self.min.row.min(pos.row), self.min.col.min(pos.col));
+        self.max = Position::new(self.max.row.max(pos.row), self.max.col.max(pos.col));
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        (self.min.row..=self.max.row).contains(&pos.row)
+            && (self.min.col..=self.max.col).contains(&pos.col)
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.col.abs_diff(self.min.col) + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.row.abs_diff(self.min.row) + 1
+    }
+}
+
+impl Add<Delta> for Position {
+    type Output = Position;
+
+    fn add(self, delta: Delta) -> Position {
+        Position::new(self.row + delta.row, self.col + delta.col)
+    }
+}
+
+impl AddAssign<Delta> for Position {
+    fn add_assign(&mut self, delta: Delta) {
+        *self = *self + delta;
+    }
+}
+
+impl Sub<Delta> for Position {
+    type Output = Position;
+
+    fn sub(self, delta: Delta) -> Position {
+        Position::new(self.row - delta.row, self.col - delta.col)
+    }
+}
+
+impl SubAssign<Delta> for Position {
+    fn sub_assign(&mut self, delta: Delta) {
+        *self = *self - delta;
+    }
+}
+
+impl Sub for Position {
+    type Output = Delta;
+
+    fn sub(self, other: Position) -> Delta {
+        Delta::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Add for Delta {
+    type Output = Delta;
+
+    fn add(self, other: Delta) -> Delta {
+        Delta::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl Sub for Delta {
+    type Output = Delta;
+
+    fn sub(self, other: Delta) -> Delta {
+        Delta::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Neg for Delta {
+    type Output = Delta;
+
+    fn neg(self) -> Delta {
+        Delta::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<isize> for Delta {
+    type Output = Delta;
+
+    fn mul(self, scale: isize) -> Delta {
+        Delta::new(self.row * scale, self.col * scale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_delta_arithmetic() {
+        let start = Position::new(2, 3);
+        let step = Delta::new(-1, 2);
+        assert_eq!(start + step, Position::new(1, 5));
+        assert_eq!(start - step, Position::new(3, 1));
+        assert_eq!(start + step * 3, Position::new(-1, 9));
+        assert_eq!(Position::new(1, 5) - start, step);
+        assert_eq!(-step, Delta::new(1, -2));
+        assert_eq!(step + step - step, step);
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut pos = Position::ORIGIN;
+        pos += Delta::new(4, 4);
+        pos -= Delta::new(1, 2);
+        assert_eq!(pos, Position::new(3, 2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Position::new(0, 0);
+        let b = Position::new(-3, 4);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(b.manhattan(a), 7);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::from_positions([
+            Position::new(2, -1),
+            Position::new(-3, 4),
+            Position::new(0, 0),
+        ])
+        .unwrap();
+        assert_eq!(bounds.min, Position::new(-3, -1));
+        assert_eq!(bounds.max, Position::new(2, 4));
+        assert_eq!((bounds.width(), bounds.height()), (6, 6));
+        assert!(bounds.contains(Position::new(-3, 4)));
+        assert!(!bounds.contains(Position::new(3, 0)));
+        assert_eq!(Bounds::from_positions([]), None);
+    }
+
+    #[test]
+    fn test_ordering_is_row_major() {
+        let mut positions = vec![
+            Position::new(1, 0),
+            Position::new(0, 5),
+            Position::new(0, -1),
+        ];
+        positions.sort();
+        assert_eq!(
+            positions,
+            [
+                Position::new(0, -1),
+                Position::new(0, 5),
+                Position::new(1, 0)
+            ]
+        );
+    }
+}
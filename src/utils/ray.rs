@@ -0,0 +1,207 @@
+use crate::utils::{
+    direction::Directions,
+    grid::Grid,
+    position::{Delta, Position},
+};
+
+/// Walks a grid from a start position by a fixed delta, yielding each cell
+/// (start included) until the walk leaves the grid
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    next: Option<Position>,
+    delta: Delta,
+    remaining: Option<usize>,
+    wrap: bool,
+}
+
+impl<'a, T> Ray<'a, T> {
+    // Stop after at most len cells
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.remaining = Some(len);
+        self
+    }
+
+    /// Wrap around the edges instead of stopping. The ray then only ends at
+    /// max_len (or a cell missing from a ragged grid), so set one.
+    pub fn wrapping(mut self) -> Self {
+        self.wrap = true;
+        self.next = self.next.map(|pos| self.grid.torus().wrap(pos));
+        self
+    }
+
+    // Ends the ray before the first cell matching the predicate, e.g. a wall
+    pub fn until(
+        self,
+        mut stop: impl FnMut(&T) -> bool,
+    ) -> impl Iterator<Item = (Position, &'a T)> {
+        self.take_while(move |(_, value)| !stop(value))
+    }
+
+    fn step(&self, pos: Position) -> Option<Position> {
+        if self.wrap {
+            return Some(self.grid.torus().advance(pos, self.delta, 1));
+        }
+
+        Some(Position::new(
+            pos.row.checked_add(self.delta.row)?,
+            pos.col.checked_add(self.delta.col)?,
+        ))
+    }
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+
+        let pos = self.next?;
+        let Some(value) = self.grid.get_at(pos) else {
+            self.next = None;
+            return None;
+        };
+
+        self.next = self.step(pos);
+        self.remaining = self.remaining.map(|n| n - 1);
+        Some((pos, value))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn ray(&self, start: Position, direction: Directions) -> Ray<'_, T> {
+        self.ray_by(start, direction.delta())
+    }
+
+    // Like ray, for arbitrary steps such as velocities or knight moves
+    pub fn ray_by(&self, start: Position, delta: Delta) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            next: Some(start),
+            delta,
+            remaining: None,
+            wrap: false,
+        }
+    }
+
+    // The n values starting at start, None if the ray leaves the grid first
+    pub fn read_ray(&self, start: Position, direction: Directions, n: usize) -> Option<Vec<T>>
+    where
+        T: Clone,
+    {
+        let values: Vec<T> = self
+            .ray(start, direction)
+            .max_len(n)
+            .map(|(_, value)| value.clone())
+            .collect();
+        (values.len() == n).then_some(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<'a>(ray: impl Iterator<Item = (Position, &'a u8)>) -> Vec<u8> {
+        ray.map(|(_, value)| *value).collect()
+    }
+
+    #[test]
+    fn test_ray_to_edge() {
+        let grid = Grid::new("abcd\nefgh\nijkl");
+        assert_eq!(
+            values(grid.ray(Position::new(0, 1), Directions::Right)),
+            b"bcd"
+        );
+        assert_eq!(
+            values(grid.ray(Position::new(2, 3), Directions::TopLeft)),
+            b"lgb"
+        );
+        assert_eq!(values(grid.ray(Position::new(0, 0), Directions::Top)), b"a");
+    }
+
+    #[test]
+    fn test_ray_positions() {
+        let grid = Grid::new("abc\ndef");
+        let positions: Vec<Position> = grid
+            .ray(Position::new(1, 0), Directions::TopRight)
+            .map(|(pos, _)| pos)
+            .collect();
+        assert_eq!(positions, [Position::new(1, 0), Position::new(0, 1)]);
+    }
+
+    #[test]
+    fn test_ray_starting_off_grid() {
+        let grid = Grid::new("abc\ndef");
+        assert_eq!(
+            grid.ray(Position::new(-1, 0), Directions::Bottom).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_ray_max_len() {
+        let grid = Grid::new("abcdef");
+        let ray = grid.ray(Position::ORIGIN, Directions::Right).max_len(3);
+        assert_eq!(values(ray), b"abc");
+    }
+
+    #[test]
+    fn test_ray_until() {
+        let grid = Grid::new("..#..");
+        let ray = grid
+            .ray(Position::ORIGIN, Directions::Right)
+            .until(|&c| c == b'#');
+        assert_eq!(values(ray), b"..");
+    }
+
+    #[test]
+    fn test_ray_by_delta() {
+        let grid = Grid::new("abcdef");
+        assert_eq!(
+            values(grid.ray_by(Position::ORIGIN, Delta::new(0, 2))),
+            b"ace"
+        );
+    }
+
+    #[test]
+    fn test_ray_wrapping() {
+        let grid = Grid::new("abc\ndef");
+        let ray = grid
+            .ray(Position::new(0, 1), Directions::Right)
+            .wrapping()
+            .max_len(5);
+        assert_eq!(values(ray), b"bcabc");
+
+        let ray = grid
+            .ray(Position::new(0, 0), Directions::TopLeft)
+            .wrapping()
+            .max_len(4);
+        assert_eq!(values(ray), b"afbd");
+    }
+
+    #[test]
+    fn test_ray_wrapping_large_delta() {
+        let grid = Grid::new("abc\ndef");
+        let ray = grid
+            .ray_by(Position::ORIGIN, Delta::new(isize::MAX, isize::MIN))
+            .wrapping()
+            .max_len(3);
+        assert_eq!(ray.count(), 3);
+    }
+
+    #[test]
+    fn test_read_ray() {
+        let grid = Grid::new("XMAS\nMMMM");
+        assert_eq!(
+            grid.read_ray(Position::ORIGIN, Directions::Right, 4),
+            Some(b"XMAS".to_vec())
+        );
+        assert_eq!(grid.read_ray(Position::ORIGIN, Directions::Bottom, 3), None);
+        assert_eq!(
+            grid.read_ray(Position::ORIGIN, Directions::Bottom, 0),
+            Some(vec![])
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use crate::utils::{
+    cells::Cells,
+    direction::{ALL_DIRECTIONS, CARDINAL_DIRECTIONS, Directions},
+    grid::Grid,
+    position::{Bounds, Position},
+};
+
+/// Which neighbours join cells into one region
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    // Orthogonal neighbours only
+    Four,
+    // Diagonal neighbours too
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(self) -> &'static [Directions] {
+        match self {
+            Connectivity::Four => &CARDINAL_DIRECTIONS,
+            Connectivity::Eight => &ALL_DIRECTIONS,
+        }
+    }
+}
+
+/// A connected set of cells. Perimeter and sides are measured along cell
+/// edges, so they're the same whichever connectivity built the region.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    // Row-major order
+    cells: Vec<Position>,
+    lookup: HashSet<Position>,
+}
+
+impl Region {
+    fn new(mut cells: Vec<Position>) -> Self {
+        cells.sort();
+        let lookup = cells.iter().copied().collect();
+        Self { cells, lookup }
+    }
+
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.lookup.contains(&pos)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Cell edges that border a cell outside the region
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|&pos| {
+                CARDINAL_DIRECTIONS
+                    .iter()
+                    .filter(|direction| !self.contains(pos + direction.delta()))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Straight runs of fence. A closed outline has as many sides as corners,
+    /// so this counts the convex and concave corners of every cell.
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    pub fn corners(&self) -> usize {
+        let mut corners = 0;
+
+        for &pos in &self.cells {
+            for direction in CARDINAL_DIRECTIONS {
+                // The corner between this side and the next one clockwise
+                let side = self.contains(pos + direction.delta());
+                let next = self.contains(pos + direction.turn_right().delta());
+                let diagonal = self.contains(pos + direction.turn_right_45().delta());
+
+                let convex = !side && !next;
+                let concave = side && next && !diagonal;
+                if convex || concave {
+                    corners += 1;
+                }
+            }
+        }
+
+        corners
+    }
+
+    // None for the empty region a flood fill returns when its seed is excluded
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::from_positions(self.cells.iter().copied())
+    }
+}
+
+/// Every cell labelled with the index of its region in regions
+#[derive(Clone, Debug)]
+pub struct Regions {
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T> Grid<T> {
+    /// Cells reachable from seed through neighbours that satisfy include,
+    /// the seed itself included when it does. None when seed is off the grid.
+    pub fn flood_fill_by(
+        &self,
+        seed: Position,
+        connectivity: Connectivity,
+        mut include: impl FnMut(Position, &T) -> bool,
+    ) -> Option<Region> {
+        let value = self.get_at(seed)?;
+        if !include(seed, value) {
+            return Some(Region::new(Vec::new()));
+        }
+
+        let mut seen = HashSet::from([seed]);
+        let mut stack = vec![seed];
+        let mut cells = Vec::new();
+        let offsets = || connectivity.directions().iter().map(|d| d.delta());
+
+        while let Some(pos) = stack.pop() {
+            cells.push(pos);
+            for (next, value) in self.neighbors_with(pos, offsets()) {
+                if !seen.contains(&next) && include(next, value) {
+                    seen.insert(next);
+                    stack.push(next);
+                }
+            }
+        }
+
+        Some(Region::new(cells))
+    }
+
+    // The region of cells equal to the seed's value
+    pub fn flood_fill(&self, seed: Position, connectivity: Connectivity) -> Option<Region>
+    where
+        T: PartialEq,
+    {
+        let value = self.get_at(seed)?;
+        self.flood_fill_by(seed, connectivity, |_, other| other == value)
+    }
+
+    // Splits the whole grid into regions of equal, connected values, in row-major order of their first cell
+    pub fn label_regions(&self, connectivity: Connectivity) -> Regions
+    where
+        T: PartialEq,
+    {
+        let mut labels = self.map(|_| usize::MAX);
+        let mut regions = Vec::new();
+
+        for (pos, _) in self.iter_2d() {
+            if labels.get_at(pos) != Some(&usize::MAX) {
+                continue;
+            }
+
+            let region = self
+                .flood_fill(pos, connectivity)
+                .expect("iter_2d positions are on the grid");
+            for &cell in region.cells() {
+                labels.set_at(cell, regions.len());
+            }
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sum of area * perimeter, and of area * sides
+    fn prices(input: &str) -> (usize, usize) {
+        let regions = Grid::new(input).label_regions(Connectivity::Four).regions;
+        let fences = regions.iter().map(|r| r.area() * r.perimeter()).sum();
+        let sides = regions.iter().map(|r| r.area() * r.sides()).sum();
+        (fences, sides)
+    }
+
+    #[test]
+    fn test_region_measurements() {
+        let regions = Grid::new("AAAA\nBBCD\nBBCC\nEEEC")
+            .label_regions(Connectivity::Four)
+            .regions;
+        let measured: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|r| (r.area(), r.perimeter(), r.sides()))
+            .collect();
+        assert_eq!(
+            measured,
+            [(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+    }
+
+    #[test]
+    fn test_nested_regions() {
+        assert_eq!(prices("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"), (772, 436));
+        assert_eq!(prices("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE").1, 236);
+        assert_eq!(
+            prices("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA").1,
+            368
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let labelled = Grid::new("ab\nba").label_regions(Connectivity::Four);
+        assert_eq!(labelled.regions.len(), 4);
+        assert_eq!(labelled.labels.get(1, 1), Some(&3));
+
+        let labelled = Grid::new("ab\nba").label_regions(Connectivity::Eight);
+        assert_eq!(labelled.regions.len(), 2);
+        assert_eq!(labelled.labels.get(1, 1), Some(&0));
+    }
+
+    #[test]
+    fn test_flood_fill_by_predicate() {
+        let grid = Grid::new("#..#\n#.##\n#..#");
+        let region = grid
+            .flood_fill_by(Position::new(0, 1), Connectivity::Four, |_, &c| c != b'#')
+            .unwrap();
+        assert_eq!(region.area(), 5);
+        let bounds = region.bounds().unwrap();
+        assert_eq!(bounds.min, Position::new(0, 1));
+        assert_eq!(bounds.max, Position::new(2, 2));
+
+        assert_eq!(
+            grid.flood_fill(Position::new(5, 5), Connectivity::Four),
+            None
+        );
+        let wall = grid.flood_fill_by(Position::ORIGIN, Connectivity::Four, |_, &c| c != b'#');
+        assert_eq!(wall.map(|r| r.bounds()), Some(None));
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io::IsTerminal;
+
+use crate::utils::{direction::Directions, grid::Grid, position::Position};
+
+/// Terminal colours for highlighted cells
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    // Cycled through when highlighting several sets, e.g. one per match
+    pub const ALL: [Color; 6] = [
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+    ];
+
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+        }
+    }
+}
+
+/// Draws a grid with overlays, coloured highlights and rulers. Colour is on
+/// when stdout is a terminal and NO_COLOR isn't set, plain text otherwise.
+/// Later overlays and highlights win where they overlap.
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    glyph: Box<dyn Fn(&T) -> char + 'a>,
+    overlays: HashMap<Position, char>,
+    highlights: HashMap<Position, Color>,
+    rulers: bool,
+    color: bool,
+}
+
+// How a byte grid cell is drawn, non-ASCII bytes show as the replacement character
+pub fn byte_glyph(&b: &u8) -> char {
+    if b.is_ascii() {
+        b as char
+    } else {
+        char::REPLACEMENT_CHARACTER
+    }
+}
+
+impl Grid {
+    // Renders each byte as its character, see byte_glyph
+    pub fn render(&self) -> Renderer<'_, u8> {
+        self.render_with(byte_glyph)
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn render_with<'a>(&'a self, glyph: impl Fn(&T) -> char + 'a) -> Renderer<'a, T> {
+        Renderer {
+            grid: self,
+            glyph: Box::new(glyph),
+            overlays: HashMap::new(),
+            highlights: HashMap::new(),
+            rulers: false,
+            color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        }
+    }
+}
+
+impl<T> Renderer<'_, T> {
+    // Draws marker in place of the cell at each position
+    pub fn overlay(mut self, positions: impl IntoIterator<Item = Position>, marker: char) -> Self {
+        for pos in positions {
+            self.overlays.insert(pos, marker);
+        }
+        self
+    }
+
+    /// Draws a path as arrows pointing at the next cell, with the last cell
+    /// left as it is. Steps between non-adjacent cells get a '*'.
+    pub fn path(mut self, cells: &[Position]) -> Self {
+        for pair in cells.windows(2) {
+            let marker = match Directions::from_delta(pair[1] - pair[0]) {
+                Some(Directions::Top) => '^',
+                Some(Directions::Right) => '>',
+                Some(Directions::Bottom) => 'v',
+                Some(Directions::Left) => '<',
+                Some(Directions::TopRight | Directions::BottomLeft) => '/',
+                Some(Directions::BottomRight | Directions::TopLeft) => '\\',
+                None => '*',
+            };
+            self.overlays.insert(pair[0], marker);
+        }
+        self
+    }
+
+    // Colours the cells at positions, plain text output shows them unchanged
+    pub fn highlight(
+        mut self,
+        positions: impl IntoIterator<Item = Position>,
+        color: Color,
+    ) -> Self {
+        for pos in positions {
+            self.highlights.insert(pos, color);
+        }
+        self
+    }
+
+    // Row numbers down the left and column numbers across the top
+    pub fn rulers(mut self, rulers: bool) -> Self {
+        self.rulers = rulers;
+        self
+    }
+
+    // Overrides the terminal detection, e.g. to force plain text for a file
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn write_column_rulers(&self, f: &mut Formatter<'_>, margin: usize) -> fmt::Result {
+        let width = self.grid.width();
+        // Tens (and higher) above the units, only where a new ten starts
+        let mut divisor = 1;
+        while divisor * 10 < width {
+            divisor *= 10;
+        }
+
+        while divisor > 0 {
+            write!(f, "{:margin$}", "")?;
+            for col in 0..width {
+                if divisor == 1 || col % divisor == 0 {
+                    write!(f, "{}", col / divisor % 10)?;
+                } else {
+                    write!(f, " ")?;
+                }
+            }
+            writeln!(f)?;
+            divisor /= 10;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Display for Renderer<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let height = self.grid.height();
+        let label_width = height.saturating_sub(1).to_string().len();
+        if self.rulers {
+            self.write_column_rulers(f, label_width + 1)?;
+        }
+
+        for row in 0..height {
+            if self.rulers {
+                write!(f, "{row:>label_width$} ")?;
+            }
+
+            for col in 0..self.grid.width() {
+                let pos = Position::new(row as isize, col as isize);
+                let Some(value) = self.grid.get_at(pos) else {
+                    break;
+                };
+                let c = self
+                    .overlays
+                    .get(&pos)
+                    .copied()
+                    .unwrap_or_else(|| (self.glyph)(value));
+
+                match self.highlights.get(&pos) {
+                    Some(color) if self.color => {
+                        write!(f, "\x1b[1;{}m{c}\x1b[0m", color.ansi_code())?
+                    }
+                    _ => write!(f, "{c}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_matches_display() {
+        let grid = Grid::new("abc\ndef");
+        let plain = grid.render().color(false).to_string();
+        assert_eq!(plain, grid.to_string());
+    }
+
+    #[test]
+    fn test_overlay_and_path() {
+        let grid = Grid::new("...\n...\n...");
+        let path = [
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 1),
+            Position::new(2, 2),
+        ];
+        let text = grid
+            .render()
+            .color(false)
+            .path(&path)
+            .overlay([Position::new(2, 0)], '#')
+            .to_string();
+        assert_eq!(text, ">v.\n.\\.\n#..\n");
+    }
+
+    #[test]
+    fn test_highlight_colors() {
+        let grid = Grid::new("ab");
+        let renderer = grid.render().highlight([Position::new(0, 1)], Color::Red);
+        assert_eq!(renderer.color(true).to_string(), "a\x1b[1;31mb\x1b[0m\n");
+
+        let plain = grid.render().highlight([Position::new(0, 1)], Color::Red);
+        assert_eq!(plain.color(false).to_string(), "ab\n");
+    }
+
+    #[test]
+    fn test_rulers() {
+        let grid = Grid::filled(12, 2, b'.');
+        let text = grid.render().color(false).rulers(true).to_string();
+        assert_eq!(
+            text,
+            "  0         1 \n  012345678901\n0 ............\n1 ............\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_glyphs_and_ragged_rows() {
+        let grid = Grid::from_chars("123\n4", |c| c.to_digit(10).unwrap());
+        let text = grid
+            .render_with(|&n| if n % 2 == 0 { 'E' } else { 'O' })
+            .color(false)
+            .to_string();
+        assert_eq!(text, "OEO\nE\n");
+    }
+}
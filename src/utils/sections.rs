@@ -0,0 +1,162 @@
+use std::str::FromStr;
+
+use crate::utils::parse::ParseError;
+
+/// A run of non-blank lines from the input. `number` is the 1-based section
+/// index and each line keeps its 1-based line number in the whole input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub number: usize,
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn error(&self, line: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(line, message).in_section(self.number)
+    }
+
+    /// Runs a line parser over every line, tagging failures with this section and the line number
+    pub fn parse_lines<T>(
+        &self,
+        parser: impl Fn(&str) -> Result<T, String>,
+    ) -> Result<Vec<T>, ParseError> {
+        self.lines
+            .iter()
+            .map(|&(line, text)| parser(text).map_err(|message| self.error(line, message)))
+            .collect()
+    }
+}
+
+/// Splits input on blank lines. CRLF endings are handled, whitespace-only lines
+/// count as blank, and runs of blank lines (including leading and trailing ones)
+/// never produce empty sections.
+pub fn split_sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section<'_>> = Vec::new();
+    let mut current: Vec<(usize, &str)> = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(Section {
+                    number: sections.len() + 1,
+                    lines: std::mem::take(&mut current),
+                });
+            }
+            continue;
+        }
+
+        // lines() only strips the \r of a \r\n pair, stray ones would break number parsing
+        current.push((index + 1, line.trim_end()));
+    }
+
+    if !current.is_empty() {
+        sections.push(Section {
+            number: sections.len() + 1,
+            lines: current,
+        });
+    }
+
+    sections
+}
+
+pub fn split_exact_sections<const N: usize>(input: &str) -> Result<[Section<'_>; N], ParseError> {
+    let sections = split_sections(input);
+    let found = sections.len();
+
+    sections.try_into().map_err(|_| {
+        ParseError::new(
+            input.lines().count(),
+            format!("expected {N} blank-line separated sections, found {found}"),
+        )
+    })
+}
+
+fn parse_value<T: FromStr>(text: &str) -> Result<T, String> {
+    let text = text.trim();
+    text.parse::<T>()
+        .map_err(|_| format!("invalid value {text:?}"))
+}
+
+/// Line parser for `X<separator>Y`, e.g. `pair::<u32>('|')` for `47|53`
+pub fn pair<T: FromStr>(separator: char) -> impl Fn(&str) -> Result<(T, T), String> {
+    move |line| {
+        let (left, right) = line
+            .split_once(separator)
+            .ok_or_else(|| format!("expected a {separator:?} separated pair in {line:?}"))?;
+        Ok((parse_value(left)?, parse_value(right)?))
+    }
+}
+
+/// Line parser for a separated list, e.g. `list::<u32>(',')` for `75,47,61`
+pub fn list<T: FromStr>(separator: char) -> impl Fn(&str) -> Result<Vec<T>, String> {
+    move |line| line.split(separator).map(parse_value).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_sections() {
+        let sections = split_sections("1|2\n3|4\n\n1,2,3\n");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(
+            sections[0].lines().collect::<Vec<_>>(),
+            [(1, "1|2"), (2, "3|4")]
+        );
+        assert_eq!(sections[1].lines().collect::<Vec<_>>(), [(4, "1,2,3")]);
+        assert_eq!(sections[1].number, 2);
+    }
+
+    #[test]
+    fn test_crlf_and_whitespace_lines() {
+        let sections = split_sections("\r\n1|2\r\n  \t\r\n\r\n1,2\r\n\r\n");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].lines().collect::<Vec<_>>(), [(2, "1|2")]);
+        assert_eq!(sections[1].lines().collect::<Vec<_>>(), [(5, "1,2")]);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert!(split_sections("").is_empty());
+        assert!(split_sections("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_exact_sections_count_mismatch() {
+        let err = split_exact_sections::<2>("1|2\n3|4").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(
+            err.message,
+            "expected 2 blank-line separated sections, found 1"
+        );
+    }
+
+    #[test]
+    fn test_pair_parser() {
+        let [section] = split_exact_sections::<1>("47|53\n97 | 13").unwrap();
+        let pairs = section.parse_lines(pair::<u32>('|')).unwrap();
+        assert_eq!(pairs, [(47, 53), (97, 13)]);
+    }
+
+    #[test]
+    fn test_list_parser_error_location() {
+        let [_, updates] = split_exact_sections::<2>("1|2\n\n1,2\n3,x,4").unwrap();
+        let err = updates.parse_lines(list::<u32>(',')).unwrap_err();
+        assert_eq!(err.section, Some(2));
+        assert_eq!(err.line, 4);
+        assert_eq!(format!("{err}"), "section 2, line 4: invalid value \"x\"");
+    }
+}
@@ -0,0 +1,99 @@
+use crate::utils::{grid::Grid, position::Position};
+
+/// Double-buffered cellular simulation, each step reads only the previous state.
+/// The two grids swap after every step so no cells are allocated after the first.
+pub struct DoubleBuffer<T> {
+    current: Grid<T>,
+    next: Grid<T>,
+    steps: usize,
+}
+
+impl<T: Clone + PartialEq> DoubleBuffer<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        let next = grid.clone();
+        Self {
+            current: grid,
+            next,
+            steps: 0,
+        }
+    }
+
+    pub fn current(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.current
+    }
+
+    // Returns whether any cell changed
+    pub fn step(&mut self, rule: impl FnMut(&Grid<T>, Position) -> T) -> bool {
+        let changed = self.current.step_into(&mut self.next, rule);
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.steps += 1;
+        changed
+    }
+
+    /// Steps until a step changes nothing, or max_steps have run.
+    /// Returns Some(steps taken) once stable, None if the limit was hit first.
+    pub fn run_until_stable(
+        &mut self,
+        max_steps: Option<usize>,
+        mut rule: impl FnMut(&Grid<T>, Position) -> T,
+    ) -> Option<usize> {
+        let start = self.steps;
+        loop {
+            if max_steps.is_some_and(|max| self.steps - start >= max) {
+                return None;
+            }
+            if !self.step(&mut rule) {
+                return Some(self.steps - start);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::position::Delta;
+
+    // Sand falls one row per step when the cell below is empty
+    fn fall(grid: &Grid<u8>, pos: Position) -> u8 {
+        let cell = *grid.get_at(pos).unwrap();
+        let below = grid.get_at(pos + Delta::new(1, 0));
+        let above = grid.get_at(pos + Delta::new(-1, 0));
+        match (cell, below, above) {
+            (b'o', Some(b'.'), _) => b'.',
+            (b'.', _, Some(b'o')) => b'o',
+            _ => cell,
+        }
+    }
+
+    #[test]
+    fn test_step() {
+        let mut sim = DoubleBuffer::new(Grid::new("o.\n..\n.."));
+        assert!(sim.step(fall));
+        assert_eq!(sim.current()._as_bytes(), b"..o...");
+        assert_eq!(sim.steps(), 1);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut sim = DoubleBuffer::new(Grid::new("o.\n..\n.."));
+        // Two moves, then one step that changes nothing
+        assert_eq!(sim.run_until_stable(None, fall), Some(3));
+        assert_eq!(sim.into_inner()._as_bytes(), b"....o.");
+    }
+
+    #[test]
+    fn test_run_until_stable_limit() {
+        let mut sim = DoubleBuffer::new(Grid::new("o.\n..\n.."));
+        assert_eq!(sim.run_until_stable(Some(1), fall), None);
+        assert_eq!(sim.current()._as_bytes(), b"..o...");
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use crate::utils::{
+    cells::Cells,
+    grid::Grid,
+    position::{Bounds, Position},
+};
+
+/// Cells keyed by signed position with no fixed size, for puzzles with huge,
+/// negative or growing coordinates. Only occupied positions have cells, and the
+/// bounding box follows them as they're inserted and removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T = u8> {
+    cells: HashMap<Position, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    // Every cell of a dense grid, e.g. to let it grow beyond its edges
+    pub fn from_grid(grid: &Grid<T>) -> Self
+    where
+        T: Clone,
+    {
+        grid.iter_2d()
+            .map(|(pos, value)| (pos, value.clone()))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Smallest rectangle holding every occupied cell, None when empty
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn get_at(&self, pos: Position) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_at_mut(&mut self, pos: Position) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    // Returns the previous value at pos, if it was occupied
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(pos),
+            None => self.bounds = Some(Bounds::new(pos)),
+        }
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Position) -> Option<T> {
+        let value = self.cells.remove(&pos)?;
+
+        // Only a cell on the edge of the box can shrink it
+        if let Some(bounds) = self.bounds {
+            let on_edge = pos.row == bounds.min.row
+                || pos.row == bounds.max.row
+                || pos.col == bounds.min.col
+                || pos.col == bounds.max.col;
+            if on_edge {
+                self.bounds = Bounds::from_positions(self.cells.keys().copied());
+            }
+        }
+
+        Some(value)
+    }
+
+    // Occupied cells in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    /// The occupied area as a dense grid, with fill for unoccupied positions.
+    /// Position (0, 0) of the grid is bounds().min.
+    pub fn to_grid(&self, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(bounds) = self.bounds else {
+            return Grid::filled(0, 0, fill);
+        };
+        let offset = bounds.min - Position::ORIGIN;
+        Grid::from_fn(bounds.width(), bounds.height(), |pos| {
+            self.get_at(pos + offset).unwrap_or(&fill).clone()
+        })
+    }
+
+    /// Draws the bounding box one text row per grid row, cell gets None for
+    /// unoccupied positions
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let mut output = String::with_capacity((bounds.width() + 1) * bounds.height());
+        for row in bounds.min.row..=bounds.max.row {
+            for col in bounds.min.col..=bounds.max.col {
+                output.push(cell(self.get_at(Position::new(row, col))));
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (pos, value) in iter {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
+
+impl<T> Cells for SparseGrid<T> {
+    type Value = T;
+
+    fn cell(&self, pos: Position) -> Option<&T> {
+        self.get_at(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{direction::Directions, pathfinding::Pathfinding};
+
+    #[test]
+    fn test_negative_and_huge_coordinates() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Position::new(-5, 3), 'a');
+        grid.insert(Position::new(1_000_000_000, -7), 'b');
+        assert_eq!(grid.get_at(Position::new(-5, 3)), Some(&'a'));
+        assert_eq!(grid.get_at(Position::new(0, 0)), None);
+        assert_eq!(grid.len(), 2);
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(bounds.min, Position::new(-5, -7));
+        assert_eq!(bounds.max, Position::new(1_000_000_000, 3));
+    }
+
+    #[test]
+    fn test_bounds_shrink_on_remove() {
+        let mut grid: SparseGrid<u8> = [
+            (Position::new(0, 0), b'#'),
+            (Position::new(2, 2), b'#'),
+            (Position::new(1, 1), b'#'),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(grid.remove(Position::new(2, 2)), Some(b'#'));
+        assert_eq!(grid.bounds().unwrap().max, Position::new(1, 1));
+        assert_eq!(grid.remove(Position::new(2, 2)), None);
+
+        grid.remove(Position::new(0, 0));
+        grid.remove(Position::new(1, 1));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: SparseGrid<char> = [(Position::new(-1, -1), 'a'), (Position::new(0, 1), 'b')]
+            .into_iter()
+            .collect();
+        let text = grid.render(|cell| cell.copied().unwrap_or('.'));
+        assert_eq!(text, "a..\n..b\n");
+        assert_eq!(grid.to_grid('.'), Grid::from_chars("a..\n..b", |c| c));
+        assert_eq!(SparseGrid::<char>::new().render(|_| '.'), "");
+    }
+
+    #[test]
+    fn test_shared_neighbor_api() {
+        let grid = SparseGrid::from_grid(&Grid::new("ab\ncd"));
+        let neighbors: Vec<u8> = grid
+            .neighbors4(Position::ORIGIN)
+            .map(|(_, &value)| value)
+            .collect();
+        assert_eq!(neighbors, b"bc");
+        assert_eq!(
+            grid.position_in_direction(Position::ORIGIN, Directions::BottomRight),
+            Some(Position::new(1, 1))
+        );
+        assert_eq!(grid.neighbors8(Position::new(-1, -1)).count(), 1);
+    }
+
+    #[test]
+    fn test_pathfinding_on_sparse_grid() {
+        // Open cells only, along an L far from the origin
+        let grid: SparseGrid<u8> = (0..5)
+            .map(|col| (Position::new(-100, col), b'.'))
+            .chain((1..4).map(|row| (Position::new(-100 + row, 4), b'.')))
+            .collect();
+        let paths = grid.bfs([Position::new(-100, 0)], |_, _| true);
+        assert_eq!(paths.distance(Position::new(-97, 4)), Some(7));
+        assert_eq!(paths.distance(Position::new(-99, 0)), None);
+    }
+}
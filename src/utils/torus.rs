@@ -0,0 +1,152 @@
+use crate::utils::{
+    cells::Cells,
+    direction::Directions,
+    grid::Grid,
+    position::{Delta, Position},
+    ray::Ray,
+};
+
+/// Wrap-around addressing for a grid: leaving one edge re-enters at the
+/// opposite one, so every position maps onto a cell. Neighbour queries through
+/// Cells wrap too and report the wrapped positions.
+#[derive(Clone, Copy, Debug)]
+pub struct Torus<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn torus(&self) -> Torus<'_, T> {
+        Torus { grid: self }
+    }
+}
+
+impl<'a, T> Torus<'a, T> {
+    fn size(&self) -> (isize, isize) {
+        (self.grid.height() as isize, self.grid.width() as isize)
+    }
+
+    // The position on the grid pos wraps onto, an empty grid leaves it as is
+    pub fn wrap(&self, pos: Position) -> Position {
+        self.advance(pos, Delta::default(), 0)
+    }
+
+    pub fn get_at(&self, pos: Position) -> Option<&'a T> {
+        self.grid.get_at(self.wrap(pos))
+    }
+
+    /// Where pos ends up after moving by velocity steps times, e.g. a robot
+    /// after t seconds. Worked out in i128 so no product can overflow.
+    pub fn advance(&self, pos: Position, velocity: Delta, steps: isize) -> Position {
+        let (height, width) = self.size();
+        let axis = |start: isize, speed: isize, size: isize| {
+            if size == 0 {
+                return start;
+            }
+            let end = start as i128 + speed as i128 * steps as i128;
+            end.rem_euclid(size as i128) as isize
+        };
+        Position::new(
+            axis(pos.row, velocity.row, height),
+            axis(pos.col, velocity.col, width),
+        )
+    }
+
+    // A ray that wraps round the edges, give it a max_len as it never leaves the grid
+    pub fn ray(&self, start: Position, direction: Directions) -> Ray<'a, T> {
+        self.grid.ray(start, direction).wrapping()
+    }
+}
+
+impl<T> Cells for Torus<'_, T> {
+    type Value = T;
+
+    fn cell(&self, pos: Position) -> Option<&T> {
+        self.get_at(pos)
+    }
+
+    fn offset(&self, pos: Position, delta: Delta) -> Option<Position> {
+        let next = self.advance(pos, delta, 1);
+        self.grid.contains(next).then_some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::pathfinding::Pathfinding;
+
+    #[test]
+    fn test_wrapping_lookups() {
+        let grid = Grid::new("abc\ndef");
+        let torus = grid.torus();
+        assert_eq!(torus.wrap(Position::new(-1, 3)), Position::new(1, 0));
+        assert_eq!(torus.get_at(Position::new(-1, -1)), Some(&b'f'));
+        assert_eq!(
+            torus.get_at(Position::new(isize::MIN, isize::MAX)),
+            grid.get(0, 1)
+        );
+    }
+
+    #[test]
+    fn test_neighbors_wrap() {
+        let grid = Grid::new("abc\ndef\nghi");
+        let torus = grid.torus();
+        let neighbors: Vec<(Position, u8)> = torus
+            .neighbors4(Position::ORIGIN)
+            .map(|(pos, &value)| (pos, value))
+            .collect();
+        assert_eq!(
+            neighbors,
+            [
+                (Position::new(2, 0), b'g'),
+                (Position::new(0, 1), b'b'),
+                (Position::new(1, 0), b'd'),
+                (Position::new(0, 2), b'c'),
+            ]
+        );
+        assert_eq!(torus.neighbors8(Position::new(1, 1)).count(), 8);
+        assert_eq!(
+            torus.position_in_direction(Position::new(2, 2), Directions::BottomRight),
+            Some(Position::ORIGIN)
+        );
+    }
+
+    #[test]
+    fn test_advance() {
+        // A robot at x=2, y=4 moving 2 right and 3 up a second, in an 11x7 room
+        let grid = Grid::filled(11, 7, 0);
+        let torus = grid.torus();
+        let end = torus.advance(Position::new(4, 2), Delta::new(-3, 2), 5);
+        assert_eq!(end, Position::new(3, 1));
+
+        let far = torus.advance(
+            Position::new(3, 3),
+            Delta::new(isize::MIN, isize::MAX),
+            isize::MAX,
+        );
+        assert!(grid.contains(far));
+    }
+
+    #[test]
+    fn test_ray_and_search_wrap() {
+        let grid = Grid::new("ab.\n...\n..c");
+        let torus = grid.torus();
+        let diagonal: Vec<u8> = torus
+            .ray(Position::new(2, 2), Directions::BottomRight)
+            .max_len(4)
+            .map(|(_, &value)| value)
+            .collect();
+        assert_eq!(diagonal, b"ca.c");
+
+        let paths = torus.bfs([Position::ORIGIN], |_, _| true);
+        assert_eq!(paths.distance(Position::new(2, 2)), Some(2));
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::new("");
+        let torus = grid.torus();
+        assert_eq!(torus.get_at(Position::new(3, -2)), None);
+        assert_eq!(torus.neighbors8(Position::ORIGIN).count(), 0);
+    }
+}
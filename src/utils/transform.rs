@@ -0,0 +1,240 @@
+use crate::utils::{grid::Grid, position::Position};
+
+/// Transforms need every row complete, so they return None for a ragged grid
+/// from Grid::new rather than dropping its partial last row
+impl<T: Clone> Grid<T> {
+    // Only the last row can be short, so its last cell settles it
+    fn is_rectangular(&self) -> bool {
+        match (self.height().checked_sub(1), self.width().checked_sub(1)) {
+            (Some(row), Some(col)) => self.get(row, col).is_some(),
+            _ => true,
+        }
+    }
+
+    // The transformed grid, built cell by cell from positions in this one
+    fn transformed(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Option<Grid<T>> {
+        if !self.is_rectangular() {
+            return None;
+        }
+        Some(Grid::from_fn(width, height, |pos| {
+            let (row, col) = source(pos.row as usize, pos.col as usize);
+            self.get(row, col)
+                .expect("transform read outside the grid")
+                .clone()
+        }))
+    }
+
+    // Rows become columns, mirroring along the main diagonal. None if ragged.
+    pub fn transpose(&self) -> Option<Grid<T>> {
+        self.transformed(self.height(), self.width(), |row, col| (col, row))
+    }
+
+    // 90° clockwise, the left column becomes the top row. None if ragged.
+    pub fn rotate_90(&self) -> Option<Grid<T>> {
+        let height = self.height();
+        self.transformed(height, self.width(), |row, col| (height - 1 - col, row))
+    }
+
+    // None if ragged
+    pub fn rotate_180(&self) -> Option<Grid<T>> {
+        let (width, height) = (self.width(), self.height());
+        self.transformed(width, height, |row, col| {
+            (height - 1 - row, width - 1 - col)
+        })
+    }
+
+    // 270° clockwise (90° counter-clockwise), the top row becomes the left column. None if ragged.
+    pub fn rotate_270(&self) -> Option<Grid<T>> {
+        let width = self.width();
+        self.transformed(self.height(), width, |row, col| (col, width - 1 - row))
+    }
+
+    // Mirror left-right, each row is reversed. None if ragged.
+    pub fn flip_horizontal(&self) -> Option<Grid<T>> {
+        let width = self.width();
+        self.transformed(width, self.height(), |row, col| (row, width - 1 - col))
+    }
+
+    // Mirror top-bottom, the row order is reversed. None if ragged.
+    pub fn flip_vertical(&self) -> Option<Grid<T>> {
+        let height = self.height();
+        self.transformed(self.width(), height, |row, col| (height - 1 - row, col))
+    }
+}
+
+/// A borrowed rectangle of a grid. Positions are relative to its top-left
+/// corner, so (0, 0) is origin in the parent grid.
+#[derive(Clone, Copy, Debug)]
+pub struct SubGrid<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Position,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T> SubGrid<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn origin(&self) -> Position {
+        self.origin
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&'a T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        self.grid.get(
+            self.origin.row as usize + row,
+            self.origin.col as usize + col,
+        )
+    }
+
+    pub fn get_at(&self, pos: Position) -> Option<&'a T> {
+        let row = usize::try_from(pos.row).ok()?;
+        let col = usize::try_from(pos.col).ok()?;
+        self.get(row, col)
+    }
+
+    // Cells in row-major order with positions relative to the view
+    pub fn iter_2d(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        (0..self.height).flat_map(move |row| {
+            (0..self.width).filter_map(move |col| {
+                let pos = Position::new(row as isize, col as isize);
+                Some((pos, self.get(row, col)?))
+            })
+        })
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |pos| {
+            self.get_at(pos)
+                .expect("sub-grid lies inside its grid")
+                .clone()
+        })
+    }
+}
+
+impl<T: PartialEq> PartialEq for SubGrid<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self
+                .iter_2d()
+                .map(|(_, v)| v)
+                .eq(other.iter_2d().map(|(_, v)| v))
+    }
+}
+
+impl<T: PartialEq> PartialEq<Grid<T>> for SubGrid<'_, T> {
+    fn eq(&self, other: &Grid<T>) -> bool {
+        self.width == other.width()
+            && self.height == other.height()
+            && self
+                .iter_2d()
+                .map(|(_, v)| v)
+                .eq(other.iter_2d().map(|(_, v)| v))
+    }
+}
+
+impl<T> Grid<T> {
+    /// The width x height rectangle with its top-left at origin, None unless
+    /// every cell of it is on the grid
+    pub fn view(&self, origin: Position, width: usize, height: usize) -> Option<SubGrid<'_, T>> {
+        let view = SubGrid {
+            grid: self,
+            origin,
+            width,
+            height,
+        };
+        if width == 0 || height == 0 {
+            return self.contains(origin).then_some(view);
+        }
+
+        let last = Position::new(
+            origin.row.checked_add((height - 1).try_into().ok()?)?,
+            origin.col.checked_add((width - 1).try_into().ok()?)?,
+        );
+        // Rows are complete apart from a ragged last one, so checking corners is enough
+        (self.contains(origin) && self.contains(last)).then_some(view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotations() {
+        let grid = Grid::new("abc\ndef");
+        let rotated = grid.rotate_90().unwrap();
+        assert_eq!(rotated, Grid::new("da\neb\nfc"));
+        assert_eq!(grid.rotate_180(), Some(Grid::new("fed\ncba")));
+        assert_eq!(grid.rotate_270(), Some(Grid::new("cf\nbe\nad")));
+        assert_eq!(rotated.rotate_270(), Some(grid.clone()));
+        assert_eq!(rotated.rotate_90(), grid.rotate_180());
+    }
+
+    #[test]
+    fn test_transpose_and_flips() {
+        let grid = Grid::new("abc\ndef");
+        let transposed = grid.transpose().unwrap();
+        assert_eq!(transposed, Grid::new("ad\nbe\ncf"));
+        assert_eq!(grid.flip_horizontal(), Some(Grid::new("cba\nfed")));
+        assert_eq!(grid.flip_vertical(), Some(Grid::new("def\nabc")));
+        assert_eq!(transposed.transpose(), Some(grid));
+    }
+
+    #[test]
+    fn test_transforms_of_ragged_and_empty_grids() {
+        let grid = Grid::new("abc\ndef\ng");
+        assert_eq!(grid.rotate_90(), None);
+        assert_eq!(grid.transpose(), None);
+        assert_eq!(grid.flip_horizontal(), None);
+        assert!(Grid::new("").rotate_90().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_view_indexing() {
+        let grid = Grid::new("abcd\nefgh\nijkl");
+        let view = grid.view(Position::new(1, 1), 2, 2).unwrap();
+        assert_eq!(view.get(0, 0), Some(&b'f'));
+        assert_eq!(view.get_at(Position::new(1, 1)), Some(&b'k'));
+        assert_eq!(view.get(0, 2), None);
+        assert_eq!(view.get_at(Position::new(-1, 0)), None);
+        assert_eq!(view.to_grid(), Grid::new("fg\njk"));
+        assert_eq!(view, Grid::new("fg\njk"));
+    }
+
+    #[test]
+    fn test_view_must_fit() {
+        let grid = Grid::new("abcd\nefgh\nij");
+        assert!(grid.view(Position::new(0, 0), 4, 2).is_some());
+        assert!(grid.view(Position::new(1, 2), 2, 2).is_none());
+        assert!(grid.view(Position::new(-1, 0), 1, 1).is_none());
+        assert!(grid.view(Position::new(0, 3), 2, 1).is_none());
+        assert!(grid.view(Position::new(0, 0), usize::MAX, 1).is_none());
+    }
+
+    #[test]
+    fn test_views_compare_by_content() {
+        let grid = Grid::new("abab\ncdcd");
+        let left = grid.view(Position::new(0, 0), 2, 2).unwrap();
+        let right = grid.view(Position::new(0, 2), 2, 2).unwrap();
+        assert_eq!(left, right);
+        assert_ne!(left, grid.view(Position::new(0, 1), 2, 2).unwrap());
+    }
+}
@@ -0,0 +1,216 @@
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::utils::{
+    grid::Grid,
+    position::Position,
+    render::{Color, byte_glyph},
+};
+
+/// A grid as it looked at one step, with cells to highlight and a caption.
+/// Cells are stored as the characters they render as, so grids of any type
+/// can be recorded side by side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    grid: Grid<char>,
+    highlights: Vec<(Vec<Position>, Color)>,
+    caption: Option<String>,
+}
+
+impl Snapshot {
+    pub fn new(grid: &Grid) -> Self {
+        Self::with_glyphs(grid, byte_glyph)
+    }
+
+    pub fn with_glyphs<T>(grid: &Grid<T>, glyph: impl FnMut(&T) -> char) -> Self {
+        Self {
+            grid: grid.map(glyph),
+            highlights: Vec::new(),
+            caption: None,
+        }
+    }
+
+    pub fn highlight(
+        mut self,
+        positions: impl IntoIterator<Item = Position>,
+        color: Color,
+    ) -> Self {
+        self.highlights
+            .push((positions.into_iter().collect(), color));
+        self
+    }
+
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Frame {
+    Snapshot(Snapshot),
+    Note(String),
+}
+
+impl Frame {
+    // Caption above the grid, colour only applies to the highlights
+    pub fn render(&self, color: bool) -> String {
+        match self {
+            Frame::Snapshot(snapshot) => {
+                let mut renderer = snapshot.grid.render_with(|&c| c).color(color);
+                for (positions, color) in &snapshot.highlights {
+                    renderer = renderer.highlight(positions.iter().copied(), *color);
+                }
+                match &snapshot.caption {
+                    Some(caption) => format!("{caption}\n{renderer}"),
+                    None => renderer.to_string(),
+                }
+            }
+            Frame::Note(text) => format!("{text}\n"),
+        }
+    }
+}
+
+impl From<Snapshot> for Frame {
+    fn from(snapshot: Snapshot) -> Self {
+        Frame::Snapshot(snapshot)
+    }
+}
+
+// Recording is per thread, so a solver's frames stay with the thread that ran it
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+// Turns recording on for the current thread
+pub fn enable() {
+    ENABLED.set(true);
+}
+
+pub fn disable() {
+    ENABLED.set(false);
+}
+
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.get()
+}
+
+/// Records the frame the closure builds. While recording is off this is a
+/// single flag check and the closure never runs, so solvers can call it in
+/// hot loops.
+#[inline]
+pub fn record<F: Into<Frame>>(frame: impl FnOnce() -> F) {
+    if is_enabled() {
+        let frame = frame().into();
+        FRAMES.with_borrow_mut(|frames| frames.push(frame));
+    }
+}
+
+pub fn note(text: impl FnOnce() -> String) {
+    record(|| Frame::Note(text()));
+}
+
+// Everything recorded on this thread so far, leaving it empty for the next run
+pub fn take_frames() -> Vec<Frame> {
+    FRAMES.take()
+}
+
+/// Plays frames one after another. On a terminal each frame is redrawn in place,
+/// in colour, with delay between them; otherwise they're printed in sequence
+/// as plain text straight away.
+pub fn replay(
+    frames: &[Frame],
+    delay: Duration,
+    terminal: bool,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    for frame in frames {
+        if terminal {
+            // Clear the screen and move the cursor home
+            write!(out, "\x1b[2J\x1b[H")?;
+        }
+        write!(out, "{}", frame.render(terminal))?;
+        out.flush()?;
+        if terminal && !delay.is_zero() {
+            thread::sleep(delay);
+        }
+    }
+    Ok(())
+}
+
+// One plain text file per frame, frame_00000.txt onwards
+pub fn write_frames(frames: &[Frame], dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        fs::write(dir.join(format!("frame_{i:05}.txt")), frame.render(false))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_skips_closures() {
+        // Each test runs on its own thread, which starts with recording off
+        assert!(!is_enabled());
+        record(|| -> Frame { panic!("built a frame while disabled") });
+        note(|| panic!("built a note while disabled"));
+        assert!(take_frames().is_empty());
+    }
+
+    #[test]
+    fn test_record_and_render() {
+        enable();
+        let grid = Grid::new("ab\ncd");
+        record(|| {
+            Snapshot::new(&grid)
+                .highlight([Position::new(1, 1)], Color::Green)
+                .caption("step 1")
+        });
+        note(|| "done".to_string());
+
+        let frames = take_frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].render(false), "step 1\nab\ncd\n");
+        assert_eq!(frames[0].render(true), "step 1\nab\nc\x1b[1;32md\x1b[0m\n");
+        assert_eq!(frames[1], Frame::Note("done".to_string()));
+
+        let snapshot = Snapshot::new(&Grid::new("aé"));
+        assert_eq!(Frame::from(snapshot).render(false), "a\u{FFFD}\u{FFFD}\n");
+        assert!(take_frames().is_empty());
+        disable();
+    }
+
+    #[test]
+    fn test_replay_and_write() {
+        let grid = Grid::from_chars("12\n34", |c| c.to_digit(10).unwrap());
+        let frames = [
+            Frame::from(Snapshot::with_glyphs(
+                &grid,
+                |&n| if n > 2 { '#' } else { '.' },
+            )),
+            Frame::Note("end".to_string()),
+        ];
+
+        // Not a terminal, so the hour-long delay is skipped
+        let mut out = Vec::new();
+        replay(&frames, Duration::from_secs(3600), false, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "..\n##\nend\n");
+
+        let dir = std::env::temp_dir().join(format!("aoc-visualize-{}", std::process::id()));
+        write_frames(&frames, &dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("frame_00001.txt")).unwrap(),
+            "end\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
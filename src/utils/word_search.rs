@@ -0,0 +1,521 @@
+use std::collections::HashSet;
+
+use crate::utils::{
+    aho_corasick::AhoCorasick,
+    direction::{ALL_DIRECTIONS, Directions},
+    grid::Grid,
+    position::{Delta, Position},
+};
+
+/// How a pattern was laid over the grid when it matched
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    // A word read from its first letter in this direction
+    Direction(Directions),
+    // A shape mirrored left-right (when flipped) and then turned clockwise
+    Turned { quarter_turns: u8, flipped: bool },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    // Index of the word or shape, in the order they were added
+    pub pattern: usize,
+    /// Where the pattern's first letter (its first non-wildcard, reading a shape
+    /// row by row) landed. Wildcards around it can fall off the grid.
+    pub start: Position,
+    pub orientation: Orientation,
+    // Cells holding the pattern's letters, wildcards aren't included
+    pub cells: Vec<Position>,
+}
+
+enum Pattern {
+    Word(Vec<u8>),
+    Shape(Vec<Vec<u8>>),
+}
+
+// One pattern in one orientation, as offsets from its first letter
+struct Placement {
+    pattern: usize,
+    orientation: Orientation,
+    cells: Vec<(Delta, u8)>,
+}
+
+/// Finds words in all 8 directions and 2D shapes in all 8 rotations and
+/// reflections. Every placement is reported, so overlapping matches each count,
+/// but a symmetric pattern (a palindrome, or a shape equal to its own rotation)
+/// only matches once per set of cells. Plain words are found by scanning each
+/// row, column and diagonal once, however many words there are.
+pub struct WordSearch {
+    patterns: Vec<Pattern>,
+    wildcard: u8,
+}
+
+impl Default for WordSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WordSearch {
+    pub const DEFAULT_WILDCARD: u8 = b'.';
+
+    pub fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+            wildcard: Self::DEFAULT_WILDCARD,
+        }
+    }
+
+    // The byte that matches any cell in words and shapes
+    pub fn wildcard(mut self, wildcard: u8) -> Self {
+        self.wildcard = wildcard;
+        self
+    }
+
+    pub fn word(mut self, word: &str) -> Self {
+        self.patterns.push(Pattern::Word(word.as_bytes().to_vec()));
+        self
+    }
+
+    /// A template of lines, e.g. "M.S\n.A.\nM.S" for an X of two MAS. Short
+    /// lines are padded with wildcards.
+    pub fn shape(mut self, template: &str) -> Self {
+        let rows = template.lines().map(|line| line.as_bytes().to_vec());
+        self.patterns.push(Pattern::Shape(rows.collect()));
+        self
+    }
+
+    // Matches ordered by start position, then by pattern
+    pub fn find_all(&self, grid: &Grid) -> Vec<Match> {
+        let mut matches = self.line_matches(grid);
+
+        for placement in self.placements() {
+            // Every placement starts with a letter, so only cells holding it can anchor a match
+            let first = placement.cells[0].1;
+            for (start, &b) in grid.iter_2d() {
+                if b != first {
+                    continue;
+                }
+                if let Some(cells) = Self::place(grid, start, &placement) {
+                    matches.push(Match {
+                        pattern: placement.pattern,
+                        start,
+                        orientation: placement.orientation,
+                        cells,
+                    });
+                }
+            }
+        }
+
+        matches.sort_by_key(|m| (m.start, m.pattern));
+        matches
+    }
+
+    pub fn count(&self, grid: &Grid) -> usize {
+        self.find_all(grid).len()
+    }
+
+    // Wildcards at either end match anything, even off the grid, so they can be dropped
+    fn trimmed<'a>(&self, word: &'a [u8]) -> &'a [u8] {
+        let start = word.iter().position(|&b| b != self.wildcard);
+        let end = word.iter().rposition(|&b| b != self.wildcard);
+        match (start, end) {
+            (Some(start), Some(end)) => &word[start..=end],
+            _ => &[],
+        }
+    }
+
+    // Wildcards need the per-cell search, everything else goes through the automaton
+    fn is_plain(&self, word: &[u8]) -> bool {
+        let word = self.trimmed(word);
+        !word.is_empty() && !word.contains(&self.wildcard)
+    }
+
+    fn line_matches(&self, grid: &Grid) -> Vec<Match> {
+        // Each word forwards and, unless it's a palindrome, backwards, so every
+        // line only needs reading in one direction
+        let mut needles: Vec<(usize, bool)> = Vec::new();
+        let mut bytes: Vec<Vec<u8>> = Vec::new();
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let Pattern::Word(word) = pattern else {
+                continue;
+            };
+            if !self.is_plain(word) {
+                continue;
+            }
+            let word = self.trimmed(word);
+            let reversed: Vec<u8> = word.iter().rev().copied().collect();
+            if reversed != word {
+                needles.push((index, true));
+                bytes.push(reversed);
+            }
+            needles.push((index, false));
+            bytes.push(word.to_vec());
+        }
+
+        let mut matches = Vec::new();
+        if needles.is_empty() {
+            return matches;
+        }
+        let automaton = AhoCorasick::new(&bytes);
+
+        for direction in LINE_DIRECTIONS {
+            for line_start in line_starts(grid, direction) {
+                let line = grid.ray(line_start, direction).map(|(_, b)| *b);
+
+                for found in automaton.find_iter(line) {
+                    let len = found.end - found.start;
+                    // A single letter reads the same along every line through it, keep rows only
+                    if len == 1 && direction != Directions::Right {
+                        continue;
+                    }
+
+                    // A reversed needle is the word read against the line, from its far end
+                    let (pattern, reversed) = needles[found.pattern];
+                    let (first, reading) = if reversed {
+                        (found.end - 1, direction.opposite())
+                    } else {
+                        (found.start, direction)
+                    };
+                    let start = line_start + direction.delta() * first as isize;
+                    let step = reading.delta();
+                    matches.push(Match {
+                        pattern,
+                        start,
+                        orientation: Orientation::Direction(reading),
+                        cells: (0..len as isize).map(|i| start + step * i).collect(),
+                    });
+                }
+            }
+        }
+
+        matches
+    }
+
+    // Positions of the placement's letters if they all match from start
+    fn place(grid: &Grid, start: Position, placement: &Placement) -> Option<Vec<Position>> {
+        placement
+            .cells
+            .iter()
+            .map(|&(delta, letter)| {
+                let pos = Position::new(
+                    start.row.checked_add(delta.row)?,
+                    start.col.checked_add(delta.col)?,
+                );
+                (grid.get_at(pos) == Some(&letter)).then_some(pos)
+            })
+            .collect()
+    }
+
+    fn placements(&self) -> Vec<Placement> {
+        let mut placements = Vec::new();
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            if matches!(pattern, Pattern::Word(word) if self.is_plain(word)) {
+                continue;
+            }
+
+            // Orientations covering the same cells with the same letters are one match
+            let mut seen = HashSet::new();
+
+            for (orientation, cells) in self.orientations(pattern) {
+                // A pattern of only wildcards would match anywhere, even off the grid
+                let Some(&(first, _)) = cells.first() else {
+                    continue;
+                };
+                if !seen.insert(normalized(&cells)) {
+                    continue;
+                }
+                let cells = cells
+                    .into_iter()
+                    .map(|(delta, letter)| (delta - first, letter))
+                    .collect();
+                placements.push(Placement {
+                    pattern: index,
+                    orientation,
+                    cells,
+                });
+            }
+        }
+
+        placements
+    }
+
+    fn orientations(&self, pattern: &Pattern) -> Vec<(Orientation, Vec<(Delta, u8)>)> {
+        match pattern {
+            Pattern::Word(word) => ALL_DIRECTIONS
+                .iter()
+                .map(|&direction| {
+                    let step = direction.delta();
+                    let cells = (0..)
+                        .zip(self.trimmed(word))
+                        .filter(|&(_, &letter)| letter != self.wildcard)
+                        .map(|(i, &letter)| (step * i, letter))
+                        .collect();
+                    (Orientation::Direction(direction), cells)
+                })
+                .collect(),
+            Pattern::Shape(rows) => {
+                let letters: Vec<(Delta, u8)> = (0..)
+                    .zip(rows)
+                    .flat_map(|(row, line)| {
+                        (0..)
+                            .zip(line)
+                            .map(move |(col, &b)| (Delta::new(row, col), b))
+                    })
+                    .filter(|&(_, letter)| letter != self.wildcard)
+                    .collect();
+
+                let mut oriented = Vec::new();
+                for flipped in [false, true] {
+                    for quarter_turns in 0..4 {
+                        let cells = letters
+                            .iter()
+                            .map(|&(delta, letter)| (turn(delta, quarter_turns, flipped), letter))
+                            .collect();
+                        let orientation = Orientation::Turned {
+                            quarter_turns,
+                            flipped,
+                        };
+                        oriented.push((orientation, cells));
+                    }
+                }
+                oriented
+            }
+        }
+    }
+}
+
+// One reading direction per family of parallel lines
+const LINE_DIRECTIONS: [Directions; 4] = [
+    Directions::Right,
+    Directions::Bottom,
+    Directions::BottomRight,
+    Directions::BottomLeft,
+];
+
+// The first cell of every line running in direction, for rows, columns and both diagonals
+fn line_starts(grid: &Grid, direction: Directions) -> Vec<Position> {
+    let height = grid.height() as isize;
+    let width = grid.width() as isize;
+    let top_row = (0..width).map(|col| Position::new(0, col));
+
+    match direction {
+        Directions::Right => (0..height).map(|row| Position::new(row, 0)).collect(),
+        Directions::Bottom => top_row.collect(),
+        Directions::BottomRight => top_row
+            .chain((1..height).map(|row| Position::new(row, 0)))
+            .collect(),
+        Directions::BottomLeft => top_row
+            .chain((1..height).map(|row| Position::new(row, width - 1)))
+            .collect(),
+        _ => unreachable!("lines are only read in LINE_DIRECTIONS"),
+    }
+}
+
+fn turn(delta: Delta, quarter_turns: u8, flipped: bool) -> Delta {
+    let mut delta = if flipped {
+        Delta::new(delta.row, -delta.col)
+    } else {
+        delta
+    };
+    for _ in 0..quarter_turns {
+        // Clockwise: right becomes down, down becomes left
+        delta = Delta::new(delta.col, -delta.row);
+    }
+    delta
+}
+
+// Cells shifted so the smallest offset is zero, placements with equal keys cover the same cells
+fn normalized(cells: &[(Delta, u8)]) -> Vec<(Delta, u8)> {
+    let min = cells
+        .iter()
+        .map(|&(delta, _)| delta)
+        .min()
+        .unwrap_or_default();
+    let mut shifted: Vec<(Delta, u8)> = cells
+        .iter()
+        .map(|&(delta, letter)| (delta - min, letter))
+        .collect();
+    shifted.sort();
+    shifted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
+
+    #[test]
+    fn test_word_count() {
+        let grid = Grid::new(EXAMPLE);
+        assert_eq!(WordSearch::new().word("XMAS").count(&grid), 18);
+    }
+
+    #[test]
+    fn test_shape_count() {
+        let grid = Grid::new(EXAMPLE);
+        let search = WordSearch::new().shape("M.S\n.A.\nM.S");
+        assert_eq!(search.count(&grid), 9);
+    }
+
+    #[test]
+    fn test_match_details() {
+        let grid = Grid::new("XMAS\n....");
+        let matches = WordSearch::new().word("XMAS").word("SAM").find_all(&grid);
+        assert_eq!(
+            matches,
+            [
+                Match {
+                    pattern: 0,
+                    start: Position::new(0, 0),
+                    orientation: Orientation::Direction(Directions::Right),
+                    cells: (0..4).map(|col| Position::new(0, col)).collect(),
+                },
+                Match {
+                    pattern: 1,
+                    start: Position::new(0, 3),
+                    orientation: Orientation::Direction(Directions::Left),
+                    cells: (1..4).rev().map(|col| Position::new(0, col)).collect(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reversed_diagonal() {
+        let grid = Grid::new("..S\n.A.\nM..");
+        let matches = WordSearch::new().word("SAM").word("MAS").find_all(&grid);
+        let found: Vec<(usize, Position, Orientation)> = matches
+            .iter()
+            .map(|m| (m.pattern, m.start, m.orientation))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    0,
+                    Position::new(0, 2),
+                    Orientation::Direction(Directions::BottomLeft)
+                ),
+                (
+                    1,
+                    Position::new(2, 0),
+                    Orientation::Direction(Directions::TopRight)
+                ),
+            ]
+        );
+        assert_eq!(
+            matches[1].cells,
+            [
+                Position::new(2, 0),
+                Position::new(1, 1),
+                Position::new(0, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_matches() {
+        let grid = Grid::new("ABABA");
+        assert_eq!(WordSearch::new().word("ABA").count(&grid), 2);
+        assert_eq!(WordSearch::new().word("AB").count(&grid), 4);
+    }
+
+    #[test]
+    fn test_palindrome_counts_once() {
+        let grid = Grid::new("ABA");
+        let matches = WordSearch::new().word("ABA").find_all(&grid);
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn test_shape_orientation() {
+        // An L, turned once clockwise
+        let grid = Grid::new("CBA\nxxx");
+        let matches = WordSearch::new().shape("A\nB.\nBC").find_all(&grid);
+        assert!(matches.is_empty());
+
+        let grid = Grid::new("BBA\nCxx");
+        let matches = WordSearch::new().shape("A\nB.\nBC").find_all(&grid);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            matches[0].orientation,
+            Orientation::Turned {
+                quarter_turns: 1,
+                flipped: false
+            }
+        );
+        assert_eq!(matches[0].start, Position::new(0, 2));
+    }
+
+    #[test]
+    fn test_custom_wildcard() {
+        let grid = Grid::new("X.S");
+        assert_eq!(WordSearch::new().word("X.S").count(&grid), 1);
+        assert_eq!(WordSearch::new().wildcard(b'?').word("X.S").count(&grid), 1);
+        assert_eq!(WordSearch::new().wildcard(b'?').word("X?S").count(&grid), 1);
+        assert_eq!(WordSearch::new().wildcard(b'?').word("X.A").count(&grid), 0);
+    }
+
+    #[test]
+    fn test_leading_wildcard_words() {
+        assert_eq!(WordSearch::new().word(".A").count(&Grid::new("BA")), 1);
+        assert_eq!(WordSearch::new().word(".A").count(&Grid::new("A")), 1);
+        assert_eq!(WordSearch::new().word("..A.").count(&Grid::new("A")), 1);
+
+        let matches = WordSearch::new().word(".A").find_all(&Grid::new("BA\nCD"));
+        assert_eq!(
+            matches,
+            [Match {
+                pattern: 0,
+                start: Position::new(0, 1),
+                orientation: Orientation::Direction(Directions::Right),
+                cells: vec![Position::new(0, 1)],
+            }]
+        );
+
+        // The wildcard before X hangs off the left edge
+        let matches = WordSearch::new().word(".X.S").find_all(&Grid::new("XMS"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].start, Position::new(0, 0));
+        assert_eq!(matches[0].cells, [Position::new(0, 0), Position::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_leading_wildcard_shapes_at_edges() {
+        assert_eq!(WordSearch::new().shape(".A").count(&Grid::new("A")), 1);
+
+        // Top: the wildcard row sits above the grid
+        let matches = WordSearch::new().shape(".\nAB").find_all(&Grid::new("AB"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].start, Position::new(0, 0));
+
+        // Left: the wildcard column sits left of the grid
+        let matches = WordSearch::new()
+            .shape(".A\n.B")
+            .find_all(&Grid::new("A\nB"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].cells, [Position::new(0, 0), Position::new(1, 0)]);
+
+        // Bottom: turned twice, the wildcard row ends up below the grid
+        let matches = WordSearch::new().shape(".\nAB").find_all(&Grid::new("BA"));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            matches[0].orientation,
+            Orientation::Turned {
+                quarter_turns: 2,
+                flipped: false
+            }
+        );
+        assert_eq!(matches[0].start, Position::new(0, 1));
+    }
+
+    #[test]
+    fn test_wildcards_only_never_match() {
+        let grid = Grid::new("abc");
+        assert_eq!(WordSearch::new().word("..").count(&grid), 0);
+        assert_eq!(WordSearch::new().word("").count(&grid), 0);
+    }
+}